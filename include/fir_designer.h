@@ -0,0 +1,120 @@
+#ifndef FIR_DESIGNER_H
+#define FIR_DESIGNER_H
+
+/* Generated with cbindgen, do not edit by hand. See src/ffi.rs. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define FIR_WINDOW_RECTANGULAR 0
+
+#define FIR_WINDOW_TRIANGULAR 1
+
+#define FIR_WINDOW_WELCH 2
+
+#define FIR_WINDOW_SIN 3
+
+#define FIR_WINDOW_HANN 4
+
+#define FIR_WINDOW_HAMMING 5
+
+#define FIR_WINDOW_BLACKMAN 6
+
+#define FIR_WINDOW_NUTTALL 7
+
+#define FIR_WINDOW_BLACKMAN_NUTTALL 8
+
+#define FIR_WINDOW_BLACKMAN_HARRIS 9
+
+#define FIR_WINDOW_FLAT_TOP 10
+
+/**
+ * Designs a lowpass filter, returning the coefficients and writing their count to `out_len`.
+ *
+ * Returns null if `window_type` is not one of the `FIR_WINDOW_*` constants or the parameters
+ * cannot produce a filter, e.g. a bandpass with equal cutoffs or a bandstop with `f_lo_cut` at DC.
+ *
+ * # Safety
+ *
+ * `out_len` must be null or valid for writes.
+ */
+double *fir_design_lowpass(double f_sampling,
+                           double f_lo_cut,
+                           double f_hi_cut,
+                           size_t len,
+                           size_t shift,
+                           int window_type,
+                           size_t *out_len);
+
+/**
+ * Designs a highpass filter, see [`fir_design_lowpass`].
+ *
+ * # Safety
+ *
+ * `out_len` must be null or valid for writes.
+ */
+double *fir_design_highpass(double f_sampling,
+                            double f_lo_cut,
+                            double f_hi_cut,
+                            size_t len,
+                            size_t shift,
+                            int window_type,
+                            size_t *out_len);
+
+/**
+ * Designs a bandpass filter, see [`fir_design_lowpass`].
+ *
+ * # Safety
+ *
+ * `out_len` must be null or valid for writes.
+ */
+double *fir_design_bandpass(double f_sampling,
+                            double f_lo_cut,
+                            double f_hi_cut,
+                            size_t len,
+                            size_t shift,
+                            int window_type,
+                            size_t *out_len);
+
+/**
+ * Designs a bandstop filter, see [`fir_design_lowpass`].
+ *
+ * # Safety
+ *
+ * `out_len` must be null or valid for writes.
+ */
+double *fir_design_bandstop(double f_sampling,
+                            double f_lo_cut,
+                            double f_hi_cut,
+                            size_t len,
+                            size_t shift,
+                            int window_type,
+                            size_t *out_len);
+
+/**
+ * Filters `signal` with the coefficients, returning an output of the same length as the signal.
+ *
+ * Returns null if any of the pointers are null.
+ *
+ * # Safety
+ *
+ * `coefficients` and `signal` must point to `len` and `signal_len` readable values and `out_len`
+ * must be valid for writes.
+ */
+double *fir_apply(const double *coefficients,
+                  size_t len,
+                  const double *signal,
+                  size_t signal_len,
+                  size_t *out_len);
+
+/**
+ * Releases an array returned by one of the design functions or [`fir_apply`].
+ *
+ * # Safety
+ *
+ * `ptr` must be null or an array returned by this interface that has not been freed yet, and `len`
+ * must be the length written to `out_len` when it was returned.
+ */
+void fir_free(double *ptr, size_t len);
+
+#endif  /* FIR_DESIGNER_H */
@@ -0,0 +1,120 @@
+//! Real-time monitoring of the filter on the default audio devices, enabled with the `audio`
+//! feature.
+//!
+//! The input stream is downmixed to mono, filtered sample by sample in the input callback and handed
+//! to the output callback through a queue, which copies the filtered signal to every output channel.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use fir_designer::fir::FilterState;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+pub struct AudioMonitor {
+    _input: cpal::Stream,
+    _output: cpal::Stream,
+    state: Arc<Mutex<FilterState>>,
+    levels: Arc<Mutex<[f32; 2]>>,
+    sample_rate: u32,
+}
+
+impl AudioMonitor {
+    /// Starts monitoring the default input through the filter on the default output.
+    pub fn start(coefficients: Vec<f64>) -> Result<Self, String> {
+        let host = cpal::default_host();
+        let input = host.default_input_device().ok_or("no audio input device")?;
+        let output = host
+            .default_output_device()
+            .ok_or("no audio output device")?;
+
+        let input_config = input.default_input_config().map_err(|e| e.to_string())?;
+        let output_config = output.default_output_config().map_err(|e| e.to_string())?;
+        if input_config.sample_format() != cpal::SampleFormat::F32
+            || output_config.sample_format() != cpal::SampleFormat::F32
+        {
+            return Err("only 32 bit float audio devices are supported".to_string());
+        }
+
+        let sample_rate = input_config.sample_rate().0;
+        let input_channels = input_config.channels() as usize;
+        let output_channels = output_config.channels() as usize;
+        let mut output_config: cpal::StreamConfig = output_config.into();
+        output_config.sample_rate = input_config.sample_rate();
+
+        let state = Arc::new(Mutex::new(FilterState::new(coefficients)));
+        let levels = Arc::new(Mutex::new([0.0; 2]));
+        // Half a second of audio at most, older samples are dropped to bound the latency
+        let max_queued = sample_rate as usize / 2;
+        let queue = Arc::new(Mutex::new(VecDeque::with_capacity(max_queued)));
+
+        let input_stream = {
+            let state = state.clone();
+            let levels = levels.clone();
+            let queue = queue.clone();
+            input
+                .build_input_stream(
+                    &input_config.into(),
+                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                        let mut state = state.lock().unwrap();
+                        let mut queue = queue.lock().unwrap();
+                        let (mut peak_in, mut peak_out) = (0.0f32, 0.0f32);
+
+                        for frame in data.chunks(input_channels) {
+                            let x = frame.iter().sum::<f32>() / frame.len() as f32;
+                            let y = state.process(x as f64) as f32;
+
+                            peak_in = peak_in.max(x.abs());
+                            peak_out = peak_out.max(y.abs());
+                            if queue.len() >= max_queued {
+                                queue.pop_front();
+                            }
+                            queue.push_back(y);
+                        }
+
+                        *levels.lock().unwrap() = [peak_in, peak_out];
+                    },
+                    |err| eprintln!("warning: audio input error: {}", err),
+                    None,
+                )
+                .map_err(|e| e.to_string())?
+        };
+
+        let output_stream = output
+            .build_output_stream(
+                &output_config,
+                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                    let mut queue = queue.lock().unwrap();
+                    for frame in data.chunks_mut(output_channels) {
+                        frame.fill(queue.pop_front().unwrap_or(0.0));
+                    }
+                },
+                |err| eprintln!("warning: audio output error: {}", err),
+                None,
+            )
+            .map_err(|e| e.to_string())?;
+
+        input_stream.play().map_err(|e| e.to_string())?;
+        output_stream.play().map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            _input: input_stream,
+            _output: output_stream,
+            state,
+            levels,
+            sample_rate,
+        })
+    }
+
+    /// Swaps the filter used by the audio thread.
+    pub fn set_coefficients(&self, coefficients: Vec<f64>) {
+        self.state.lock().unwrap().set_coefficients(coefficients);
+    }
+
+    /// Returns the peak `[input, output]` levels of the last processed block.
+    pub fn levels(&self) -> [f32; 2] {
+        *self.levels.lock().unwrap()
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
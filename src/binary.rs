@@ -0,0 +1,179 @@
+//! Compact binary file format of a [`FilterDef`].
+//!
+//! A file consists of the magic number `FIRd`, a version byte and the [`bincode`] serialization of
+//! the design. Floating point values are stored bit for bit.
+//!
+//! Version 1 files, written before [`FilterDef::normalization`] was added, are still read and get
+//! the default normalization. Version 2 files, written before [`FilterDef::delay_offset`] was added,
+//! are read without an offset.
+
+use super::fir::{Filter, FilterDef, Normalization, Window, WindowMode};
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Identifies a binary filter design file.
+pub const MAGIC: &[u8; 4] = b"FIRd";
+/// Version of the format written by [`FilterDef::to_binary_file`].
+pub const VERSION: u8 = 3;
+
+/// Layout of [`FilterDef`] in version 1 files.
+#[derive(Deserialize)]
+struct FilterDefV1 {
+    filter: Filter,
+    window: Window,
+    window_mode: WindowMode,
+    len: usize,
+    shift: usize,
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    force_symmetry: bool,
+}
+
+impl From<FilterDefV1> for FilterDef {
+    fn from(def: FilterDefV1) -> Self {
+        FilterDef {
+            filter: def.filter,
+            window: def.window,
+            window_mode: def.window_mode,
+            len: def.len,
+            shift: def.shift,
+            f_sampling: def.f_sampling,
+            f_lo_cut: def.f_lo_cut,
+            f_hi_cut: def.f_hi_cut,
+            force_symmetry: def.force_symmetry,
+            ..Default::default()
+        }
+    }
+}
+
+/// Layout of [`FilterDef`] in version 2 files.
+#[derive(Deserialize)]
+struct FilterDefV2 {
+    filter: Filter,
+    window: Window,
+    window_mode: WindowMode,
+    len: usize,
+    shift: usize,
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    force_symmetry: bool,
+    normalization: Normalization,
+}
+
+impl From<FilterDefV2> for FilterDef {
+    fn from(def: FilterDefV2) -> Self {
+        FilterDef {
+            filter: def.filter,
+            window: def.window,
+            window_mode: def.window_mode,
+            len: def.len,
+            shift: def.shift,
+            f_sampling: def.f_sampling,
+            f_lo_cut: def.f_lo_cut,
+            f_hi_cut: def.f_hi_cut,
+            force_symmetry: def.force_symmetry,
+            normalization: def.normalization,
+            ..Default::default()
+        }
+    }
+}
+
+impl FilterDef {
+    /// Writes the design to a binary file, see the [module](self) docs.
+    pub fn to_binary_file(&self, path: &Path) -> Result<(), io::Error> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bincode::serialize_into(&mut bytes, self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        fs::write(path, bytes)
+    }
+
+    /// Reads a design from a binary file written by [`FilterDef::to_binary_file`].
+    ///
+    /// Files without the magic number or of an unknown version are rejected with
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn from_binary_file(path: &Path) -> Result<FilterDef, io::Error> {
+        let bytes = fs::read(path)?;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let body = bytes
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| invalid("not a binary filter design file".to_string()))?;
+        match body.split_first() {
+            Some((&VERSION, body)) => {
+                bincode::deserialize(body).map_err(|e| invalid(e.to_string()))
+            }
+            Some((1, body)) => bincode::deserialize::<FilterDefV1>(body)
+                .map(FilterDef::from)
+                .map_err(|e| invalid(e.to_string())),
+            Some((2, body)) => bincode::deserialize::<FilterDefV2>(body)
+                .map(FilterDef::from)
+                .map_err(|e| invalid(e.to_string())),
+            Some((version, _)) => Err(invalid(format!("unsupported version {}", version))),
+            None => Err(invalid("missing version".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_round_trip_is_bitwise() {
+        let def = FilterDef {
+            filter: Filter::Multiband {
+                bands: vec![(0.0, 0.1 + 0.2, true), (0.1 + 0.2, 1.0 / 3.0, false)],
+            },
+            window: Window::Dpss {
+                half_bandwidth: 1.0 / 7.0,
+            },
+            window_mode: WindowMode::Periodic,
+            len: 47,
+            shift: 23,
+            f_sampling: 44100.0 / 3.0,
+            f_lo_cut: -0.0,
+            f_hi_cut: 1e-300,
+            force_symmetry: true,
+            normalization: Normalization::UnitSum,
+            delay_offset: -5,
+        };
+        let path = std::env::temp_dir().join(format!("fir-designer-{}.fird", std::process::id()));
+
+        def.to_binary_file(&path).unwrap();
+        let read = FilterDef::from_binary_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(read == def);
+        let Filter::Multiband { bands } = &read.filter else {
+            panic!("filter type changed");
+        };
+        let Window::Dpss { half_bandwidth } = read.window else {
+            panic!("window type changed");
+        };
+        let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<u64>>();
+        assert_eq!(
+            bits(&[
+                read.f_sampling,
+                read.f_lo_cut,
+                read.f_hi_cut,
+                half_bandwidth
+            ]),
+            bits(&[def.f_sampling, def.f_lo_cut, def.f_hi_cut, 1.0 / 7.0])
+        );
+        assert_eq!(
+            bits(
+                &bands
+                    .iter()
+                    .flat_map(|(lo, hi, _)| [*lo, *hi])
+                    .collect::<Vec<_>>()
+            ),
+            bits(&[0.0, 0.1 + 0.2, 0.1 + 0.2, 1.0 / 3.0])
+        );
+    }
+}
@@ -0,0 +1,1034 @@
+//! Plot data and metrics derived from a [`FilterDef`].
+
+use super::fir::{convolve, FilterDef, FilterError, Window, DFT_LEN};
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Number of frequencies the coefficient sensitivity is evaluated at.
+const SENSITIVITY_POINTS: usize = 256;
+/// Passband deviation allowed by the recommended coefficient word length.
+const WORD_LENGTH_TOLERANCE_DB: f64 = 0.1;
+/// Magnitude relative to the largest coefficient below which edge taps are not significant.
+pub const SIGNIFICANT_TAP_THRESHOLD: f64 = 1e-3;
+/// Largest symmetry error, relative to the largest coefficient, of a linear phase filter.
+const LINEAR_PHASE_TOLERANCE: f64 = 1e-9;
+/// Deviation from unity gain allowed within the passband reported in [`FilterData`].
+pub const SPEC_PASSBAND_RIPPLE_DB: f64 = 0.5;
+/// Attenuation the stopband reported in [`FilterData`] starts at.
+pub const SPEC_STOPBAND_ATTENUATION_DB: f64 = 40.0;
+/// Windows compared by [`recommend_window`].
+const RECOMMENDED_WINDOWS: [Window; 11] = [
+    Window::Rectangular,
+    Window::Triangular,
+    Window::Welch,
+    Window::Sin,
+    Window::Hann,
+    Window::Hamming,
+    Window::Blackman,
+    Window::Nuttall,
+    Window::BlackmanNuttall,
+    Window::BlackmanHarris,
+    Window::FlatTop,
+];
+/// Number of 10% length increases [`recommend_window`] tries, about doubling the estimate.
+const RECOMMENDATION_GROWTH_STEPS: usize = 7;
+
+/// Linear phase FIR filter type given by the symmetry and length of the coefficients.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FirType {
+    /// Symmetric, odd length.
+    I,
+    /// Symmetric, even length.
+    II,
+    /// Antisymmetric, odd length.
+    III,
+    /// Antisymmetric, even length.
+    IV,
+}
+
+impl fmt::Display for FirType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::I => write!(f, "Type I"),
+            Self::II => write!(f, "Type II"),
+            Self::III => write!(f, "Type III"),
+            Self::IV => write!(f, "Type IV"),
+        }
+    }
+}
+
+/// Performance metrics of the windowed filter.
+#[derive(Clone, Debug, Default)]
+pub struct FilterMetrics {
+    pub passband_ripple_db: f64,
+    pub stopband_attenuation_db: f64,
+    /// See [`FilterDef::actual_3db_hz`].
+    pub actual_3db_hz: f64,
+    /// Mean width of the transition bands.
+    pub transition_width_hz: f64,
+    /// Gain at the passband center relative to the smallest passband gain, see
+    /// [`FilterDef::compute_passband_correction_factor`].
+    pub passband_droop_db: f64,
+    /// See [`FilterDef::frequency_response_l2_error`].
+    pub l2_error: f64,
+    /// See [`FilterDef::frequency_response_inf_error`].
+    pub inf_error: f64,
+    /// Group delay at the passband center.
+    pub group_delay_samples: f64,
+    /// Mean passband group delay minus the delay expected from the shift.
+    pub group_delay_deviation_samples: f64,
+    /// See [`FilterDef::group_delay_variation_db`].
+    pub group_delay_variation_db: f64,
+    /// Width of an ideal one sided passband with the gain at the passband center that passes the
+    /// same white noise power as the filter.
+    pub equivalent_noise_bandwidth_hz: f64,
+    /// `None` if the coefficients are neither symmetric nor antisymmetric.
+    pub fir_type: Option<FirType>,
+    /// Largest deviation from symmetry or antisymmetry, whichever is closer, relative to the largest
+    /// coefficient.
+    pub symmetry_error: f64,
+    pub is_linear_phase: bool,
+    pub dc_gain_db: f64,
+    /// See [`FilterDef::gain_at_nyquist_db`].
+    pub nyquist_gain_db: f64,
+    pub max_coefficient: f64,
+    pub min_coefficient: f64,
+    /// Ratio of the largest to the smallest non-zero coefficient magnitude.
+    pub dynamic_range_db: f64,
+    /// Bits above the binary point, including the sign bit, needed to hold the largest coefficient
+    /// `$\lceil \log_2 \max |h| \rceil + 1$`. Negative for coefficients well below 1, where the
+    /// leading fractional bits only repeat the sign.
+    pub required_fractional_bits: i32,
+}
+
+/// Frequency response of a signal as `[Hz, value]` pairs from DC up to the Nyquist frequency.
+#[derive(Clone, Debug, Default)]
+pub struct DftResult {
+    pub magnitude_db: Vec<[f64; 2]>,
+    pub phase_rad: Vec<[f64; 2]>,
+    /// Group delay in samples.
+    pub group_delay: Vec<[f64; 2]>,
+}
+
+#[derive(Default)]
+pub struct FilterData {
+    pub filter: Vec<f64>,
+    pub window: Vec<f64>,
+    pub f_windowed: Vec<f64>,
+    pub filter_imp: Vec<[f64; 2]>,
+    pub filter_stp: Vec<[f64; 2]>,
+    pub filter_dft: DftResult,
+    pub window_fun: Vec<[f64; 2]>,
+    pub window_dft: DftResult,
+    pub f_windowed_imp: Vec<[f64; 2]>,
+    pub f_windowed_stp: Vec<[f64; 2]>,
+    pub f_windowed_dft: DftResult,
+    /// Magnitude in dB of the windowed filter corrected for the coherent gain of the window.
+    pub f_windowed_dft_corrected: Vec<[f64; 2]>,
+    pub f_windowed_psd: Vec<[f64; 2]>,
+    pub phase_response: Vec<[f64; 2]>,
+    pub ideal_phase: Vec<[f64; 2]>,
+    pub f_normalized_imp: Vec<[f64; 2]>,
+    pub f_normalized_stp: Vec<[f64; 2]>,
+    pub autocorrelation: Vec<[f64; 2]>,
+    pub autocorrelation_symmetric: bool,
+    pub bandwidth_3db: f64,
+    pub metrics: FilterMetrics,
+    pub aliasing_artifact: f64,
+    pub coefficient_sensitivity: f64,
+    pub word_length: u32,
+    pub significant_taps: usize,
+    /// Taps above [`SIGNIFICANT_TAP_THRESHOLD`] anywhere in the filter, see [`FilterDef::to_sparse`].
+    pub sparse_taps: usize,
+    /// See [`crate::fir::SparseFir::error_bound_db`].
+    pub sparse_error_bound_db: f64,
+    /// Passband edge for [`SPEC_PASSBAND_RIPPLE_DB`], see [`FilterDef::passband_end_hz`].
+    pub passband_end_hz: Option<f64>,
+    /// Stopband edge for [`SPEC_STOPBAND_ATTENUATION_DB`], see [`FilterDef::stopband_start_hz`].
+    pub stopband_start_hz: Option<f64>,
+    pub quantization_16bit: QuantizationAnalysis,
+}
+
+/// Fails for the designs rejected by [`FilterDef::validate`], whose coefficients are NaN.
+impl TryFrom<&FilterDef> for FilterData {
+    type Error = FilterError;
+
+    fn try_from(def: &FilterDef) -> Result<Self, FilterError> {
+        let mut data = FilterData::from_coefficients(
+            def.compute_sinc_coefficients(),
+            def.compute_window(),
+            def.try_compute_filter()?,
+            def.f_sampling,
+        );
+        // The metrics share the coefficients and the response computed for the plots
+        let (f_windowed, dft) = (&data.f_windowed, &data.f_windowed_dft);
+        data.metrics = def.compute_all_metrics_of(f_windowed, dft);
+        data.aliasing_artifact = def.compute_aliasing_artifact_db_of(f_windowed);
+        data.passband_end_hz = def.passband_end_hz_of(f_windowed, SPEC_PASSBAND_RIPPLE_DB);
+        data.stopband_start_hz = def.stopband_start_hz_of(f_windowed, SPEC_STOPBAND_ATTENUATION_DB);
+        data.quantization_16bit = def.quantization_analysis_16bit_of(f_windowed, dft);
+        data.ideal_phase =
+            FilterDef::compute_ideal_linear_phase(def.shift, def.f_sampling, DFT_LEN / 2);
+
+        Ok(data)
+    }
+}
+
+impl FilterData {
+    /// Returns the plot data of two filters in series, whose impulse response is the convolution of
+    /// the impulse responses of the individual filters.
+    ///
+    /// The cascade has no window of its own, its window is all ones. The metrics and band edges
+    /// depend on the ideal response of a [`FilterDef`] and are left at their defaults, the aliasing
+    /// artifact is NaN.
+    pub fn cascade(a: &FilterData, b: &FilterData, f_sampling: f64) -> FilterData {
+        let f_windowed = convolve(&a.f_windowed, &b.f_windowed);
+        let window = vec![1.0; f_windowed.len()];
+
+        FilterData::from_coefficients(
+            convolve(&a.filter, &b.filter),
+            window,
+            f_windowed,
+            f_sampling,
+        )
+    }
+
+    /// Returns the plot data of coefficients that were not designed from a [`FilterDef`], e.g.
+    /// coefficients read from a file.
+    ///
+    /// The coefficients are both the filter and the windowed filter and the window is all ones. As
+    /// with [`FilterData::cascade`] the metrics and band edges are left at their defaults and the
+    /// aliasing artifact is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::data::FilterData;
+    ///
+    /// let data = FilterData::from_raw(vec![0.25, 0.5, 0.25], 1000.0);
+    ///
+    /// assert_eq!(data.f_windowed, data.filter);
+    /// assert_eq!(data.window, vec![1.0; 3]);
+    /// assert!(data.f_windowed_dft.magnitude_db[0][1].abs() < 1e-12);
+    /// ```
+    pub fn from_raw(coefficients: Vec<f64>, f_sampling: f64) -> FilterData {
+        let window = vec![1.0; coefficients.len()];
+
+        FilterData::from_coefficients(coefficients.clone(), window, coefficients, f_sampling)
+    }
+
+    /// Computes the plot data that only depends on the coefficients. The ideal phase assumes a
+    /// delay of half the filter length.
+    fn from_coefficients(
+        filter: Vec<f64>,
+        window: Vec<f64>,
+        f_windowed: Vec<f64>,
+        f_sampling: f64,
+    ) -> Self {
+        let filter_imp = plot_filter_imp(&filter, f_sampling);
+        let filter_stp = plot_filter_stp(&filter, f_sampling);
+        let filter_dft = plot_dft(&filter, f_sampling);
+        let window_fun = plot_window(&window, f_sampling);
+        let window_dft = plot_dft(&window, f_sampling);
+        let f_windowed_imp = plot_filter_imp(&f_windowed, f_sampling);
+        let f_windowed_stp = plot_filter_stp(&f_windowed, f_sampling);
+        let f_windowed_dft = plot_dft(&f_windowed, f_sampling);
+        let f_windowed_dft_corrected =
+            apply_window_correction(&f_windowed_dft.magnitude_db, window_coherent_gain(&window));
+        let f_windowed_psd = plot_psd(&f_windowed_dft.magnitude_db, f_windowed.len(), f_sampling);
+        let phase_response = unwrap_phase(&f_windowed_dft.phase_rad);
+        let ideal_phase = FilterDef::compute_ideal_linear_phase(
+            f_windowed.len().saturating_sub(1) / 2,
+            f_sampling,
+            DFT_LEN / 2,
+        );
+        let f_normalized = Window::apply_and_normalize(&filter, &window);
+        let f_normalized_imp = plot_window(&f_normalized, f_sampling);
+        let f_normalized_stp = plot_filter_stp(&f_normalized, f_sampling);
+        let autocorrelation = FilterDef::compute_autocorrelation(&f_windowed);
+        let autocorrelation_symmetric = FilterDef::is_autocorrelation_symmetric(&autocorrelation);
+        let bandwidth_3db = FilterDef::compute_bandwidth_3db(&f_windowed_dft.magnitude_db);
+        let lsb_16 = f_windowed
+            .iter()
+            .fold(0.0, |peak: f64, h| peak.max(h.abs()))
+            * 2f64.powi(-15);
+        let coefficient_sensitivity =
+            FilterDef::coefficient_sensitivity(&f_windowed, lsb_16, SENSITIVITY_POINTS);
+        let word_length = FilterDef::recommended_word_length(&f_windowed, WORD_LENGTH_TOLERANCE_DB);
+        let significant_taps =
+            FilterDef::truncate_to_significant_taps(&f_windowed, SIGNIFICANT_TAP_THRESHOLD)
+                .0
+                .len();
+        let sparse_fir = FilterDef::to_sparse(&f_windowed, SIGNIFICANT_TAP_THRESHOLD);
+
+        Self {
+            filter,
+            window,
+            f_windowed,
+            filter_imp,
+            filter_stp,
+            filter_dft,
+            window_fun,
+            window_dft,
+            f_windowed_imp,
+            f_windowed_stp,
+            f_windowed_dft,
+            f_windowed_dft_corrected,
+            f_windowed_psd,
+            phase_response,
+            ideal_phase,
+            f_normalized_imp,
+            f_normalized_stp,
+            autocorrelation,
+            autocorrelation_symmetric,
+            bandwidth_3db,
+            metrics: FilterMetrics::default(),
+            aliasing_artifact: f64::NAN,
+            coefficient_sensitivity,
+            word_length,
+            significant_taps,
+            sparse_taps: sparse_fir.tap_count(),
+            sparse_error_bound_db: sparse_fir.error_bound_db(),
+            passband_end_hz: None,
+            stopband_start_hz: None,
+            quantization_16bit: QuantizationAnalysis::default(),
+        }
+    }
+}
+
+/// Removes the `$2\pi$` jumps from a phase response given as `[Hz, rad]` pairs.
+fn unwrap_phase(phase: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    let mut offset = 0.0;
+    let mut phase_prev: Option<f64> = None;
+
+    phase
+        .iter()
+        .map(|[f, phase]| {
+            if let Some(phase_prev) = phase_prev {
+                let delta = phase - phase_prev;
+                if delta > PI {
+                    offset -= 2.0 * PI;
+                } else if delta < -PI {
+                    offset += 2.0 * PI;
+                }
+            }
+            phase_prev = Some(*phase);
+
+            [*f, phase + offset]
+        })
+        .collect()
+}
+
+impl FilterDef {
+    /// Returns the performance metrics of the windowed filter, evaluated on the response returned by
+    /// [`plot_dft`].
+    pub fn compute_all_metrics(&self) -> FilterMetrics {
+        let f_windowed = self.compute_filter();
+        let dft = plot_dft(&f_windowed, self.f_sampling);
+
+        self.compute_all_metrics_of(&f_windowed, &dft)
+    }
+
+    /// Returns the performance metrics of the windowed coefficients `f_windowed` and their response
+    /// `dft` returned by [`plot_dft`], see [`FilterDef::compute_all_metrics`].
+    pub fn compute_all_metrics_of(&self, f_windowed: &[f64], dft: &DftResult) -> FilterMetrics {
+        let dft = &dft.magnitude_db;
+
+        let passband_ripple_db = self.compute_passband_ripple(dft);
+        let stopband_attenuation_db = self.compute_stopband_attenuation(dft);
+
+        // The transition bands are what lies between the lowest passband level and the largest
+        // stopband side lobe
+        let passband_max = dft
+            .iter()
+            .filter(|[f, _]| self.is_passband(*f))
+            .fold(f64::NEG_INFINITY, |max, [_, db]| max.max(*db));
+        let transitions = dft
+            .windows(2)
+            .filter(|p| self.is_passband(p[0][0]) != self.is_passband(p[1][0]))
+            .count();
+        let df = self.f_sampling / DFT_LEN as f64;
+        let transition_width_hz = dft
+            .iter()
+            .filter(|[_, db]| {
+                *db < passband_max - passband_ripple_db && *db > -stopband_attenuation_db
+            })
+            .count() as f64
+            * df
+            / transitions.max(1) as f64;
+
+        let (re, im) = self.frequency_response_of(f_windowed, self.passband_center_hz());
+        let passband_droop_db = 10.0 * (re.powi(2) + im.powi(2)).log10()
+            - 20.0
+                * self
+                    .compute_passband_correction_factor_of(f_windowed)
+                    .log10();
+        let energy: f64 = f_windowed.iter().map(|h| h.powi(2)).sum();
+        let equivalent_noise_bandwidth_hz =
+            self.f_sampling * energy / (2.0 * (re.powi(2) + im.powi(2)));
+
+        let peak = f_windowed
+            .iter()
+            .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+        let asymmetry = |sign: f64| {
+            f_windowed
+                .iter()
+                .zip(f_windowed.iter().rev())
+                .fold(0.0, |max: f64, (a, b)| max.max((a - sign * b).abs()))
+                / peak
+        };
+        let (symmetric_error, antisymmetric_error) = if peak == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (asymmetry(1.0), asymmetry(-1.0))
+        };
+        let symmetry_error = symmetric_error.min(antisymmetric_error);
+        let is_linear_phase = symmetry_error <= LINEAR_PHASE_TOLERANCE;
+        let is_odd = !f_windowed.len().is_multiple_of(2);
+        let fir_type = match (
+            is_linear_phase,
+            symmetric_error <= antisymmetric_error,
+            is_odd,
+        ) {
+            (false, _, _) => None,
+            (true, true, true) => Some(FirType::I),
+            (true, true, false) => Some(FirType::II),
+            (true, false, true) => Some(FirType::III),
+            (true, false, false) => Some(FirType::IV),
+        };
+
+        let min_nonzero = f_windowed
+            .iter()
+            .filter(|h| **h != 0.0)
+            .fold(f64::INFINITY, |min, h| min.min(h.abs()));
+        let dynamic_range_db = if min_nonzero.is_finite() {
+            20.0 * (peak / min_nonzero).log10()
+        } else {
+            0.0
+        };
+
+        FilterMetrics {
+            passband_ripple_db,
+            stopband_attenuation_db,
+            transition_width_hz,
+            passband_droop_db,
+            l2_error: self.frequency_response_l2_error_of(f_windowed, DFT_LEN),
+            inf_error: self.frequency_response_inf_error_of(f_windowed, DFT_LEN),
+            group_delay_samples: FilterDef::compute_group_delay_at(
+                f_windowed,
+                self.passband_center_hz(),
+                self.f_sampling,
+            ),
+            group_delay_deviation_samples: self.actual_group_delay_passband_samples_of(f_windowed)
+                - self.expected_group_delay_samples(),
+            group_delay_variation_db: self.group_delay_variation_db_of(f_windowed),
+            equivalent_noise_bandwidth_hz,
+            fir_type,
+            symmetry_error,
+            is_linear_phase,
+            actual_3db_hz: self.actual_3db_hz_of(f_windowed),
+            dc_gain_db: 20.0 * FilterDef::compute_dc_gain(f_windowed).abs().log10(),
+            nyquist_gain_db: self.gain_at_nyquist_db_of(f_windowed),
+            max_coefficient: f_windowed.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            min_coefficient: f_windowed.iter().copied().fold(f64::INFINITY, f64::min),
+            dynamic_range_db,
+            required_fractional_bits: peak.log2().ceil() as i32 + 1,
+        }
+    }
+
+    /// Returns the change of the frequency response of the windowed filter when its coefficients
+    /// are rounded to 16 bit signed integers, scaled so the largest coefficient maps onto full
+    /// scale, and converted back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{FilterDef, Window};
+    ///
+    /// let def = FilterDef {
+    ///     window: Window::Hamming,
+    ///     len: 32,
+    ///     shift: 16,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// assert!(def.quantization_analysis_16bit().worst_case_passband_error_db < 0.1);
+    /// ```
+    pub fn quantization_analysis_16bit(&self) -> QuantizationAnalysis {
+        let f_windowed = self.compute_filter();
+        let dft = plot_dft(&f_windowed, self.f_sampling);
+
+        self.quantization_analysis_16bit_of(&f_windowed, &dft)
+    }
+
+    /// Returns the 16 bit quantization analysis of the windowed coefficients `f_windowed` and their
+    /// response `dft` returned by [`plot_dft`], see [`FilterDef::quantization_analysis_16bit`].
+    pub fn quantization_analysis_16bit_of(
+        &self,
+        f_windowed: &[f64],
+        dft: &DftResult,
+    ) -> QuantizationAnalysis {
+        let peak = f_windowed
+            .iter()
+            .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+        let scale = i16::MAX as f64 / peak;
+        let f_quantized: Vec<f64> = f_windowed
+            .iter()
+            .map(|h| (h * scale).round() / scale)
+            .collect();
+
+        let dft = &dft.magnitude_db;
+        let dft_quantized = plot_dft(&f_quantized, self.f_sampling).magnitude_db;
+
+        let error: Vec<f64> = f_windowed
+            .iter()
+            .zip(&f_quantized)
+            .map(|(h, q)| q - h)
+            .collect();
+        let worst_case_magnitude_error_db = plot_dft(&error, self.f_sampling)
+            .magnitude_db
+            .iter()
+            .fold(f64::NEG_INFINITY, |max, [_, db]| max.max(*db));
+        let worst_case_passband_error_db = dft
+            .iter()
+            .zip(&dft_quantized)
+            .filter(|([f, _], _)| self.is_passband(*f))
+            .fold(0.0, |max: f64, ([_, db], [_, db_quantized])| {
+                max.max((db_quantized - db).abs())
+            });
+        let worst_case_stopband_error_db = self.compute_stopband_attenuation(dft)
+            - self.compute_stopband_attenuation(&dft_quantized);
+
+        QuantizationAnalysis {
+            worst_case_magnitude_error_db,
+            worst_case_passband_error_db,
+            worst_case_stopband_error_db,
+        }
+    }
+}
+
+pub fn plot_window(w: &[f64], f_sampling: f64) -> Vec<[f64; 2]> {
+    let dt = 1.0 / f_sampling;
+
+    let mut n = 0;
+    w.iter()
+        .map(|w| {
+            let t = n as f64 * dt;
+            n += 1;
+
+            [t, *w]
+        })
+        .collect()
+}
+
+pub fn plot_filter_imp(f: &[f64], f_sampling: f64) -> Vec<[f64; 2]> {
+    let dt = 1.0 / f_sampling;
+
+    let mut n = 0;
+    f.iter()
+        .map(|f| {
+            let t = n as f64 * dt;
+            n += 1;
+
+            [t, f * dt]
+        })
+        .collect()
+}
+
+/// Returns the step response of the filter.
+///
+/// The step response of a FIR filter is the running sum of its coefficients, so the final value is
+/// the DC gain of the filter.
+pub fn plot_filter_stp(f: &[f64], f_sampling: f64) -> Vec<[f64; 2]> {
+    let dt = 1.0 / f_sampling;
+
+    let mut n = 0;
+    let mut y = 0.0;
+    f.iter()
+        .map(|f| {
+            let t = n as f64 * dt;
+            n += 1;
+
+            y += f;
+
+            [t, y]
+        })
+        .collect()
+}
+
+/// Returns the magnitude in dB, the phase and the group delay of the DFT of a signal.
+///
+/// The index `$m$` runs from 0 to `$\frac{N}{2}$`. This automatically discards the negative frequency
+/// components produced by the DFT. Considering that for this use case, the filter length will be less
+/// than `$N$`, the index `$n$` running over the filter length effectly results in a zero padded signal.
+///
+/// Instead of using the complex function:
+///
+/// ```math
+/// e^{-j2\pi nm/N}
+/// ```
+/// Eulers formula is used:
+/// ```math
+/// c_m[n] = \cos(2\pi mn/N) \\
+/// s_m[n] = \sin(2\pi mn/N)
+/// ```
+///
+/// The group delay is evaluated in the same pass without differentiating the phase as
+///
+/// ```math
+/// \tau_m = \Re\left\{\frac{\sum_n n x[n] e^{-j2\pi nm/N}}{\sum_n x[n] e^{-j2\pi nm/N}}\right\}
+/// ```
+///
+/// [\[1\]](https://hal.science/hal-04075823/document) Laurent Nony, Jean-Marc Themlin.
+/// An introduction to the Discrete Fourier Transform and its applications in signal processing. Master. France. 2023. hal-04075823
+///
+/// [\[2\]](http://www.dspguide.com/pdfbook.htm) Steven W. Smith.
+/// The Scientist and Engineer's Guide to Digital Signal Processing
+pub fn plot_dft(signal: &[f64], f_sampling: f64) -> DftResult {
+    let df = f_sampling / (DFT_LEN) as f64;
+    let mut result = DftResult::default();
+
+    for m in 0..DFT_LEN / 2 {
+        let f = m as f64 * df;
+
+        let mut n = 0;
+        let (re, im, re_n, im_n) = signal.iter().fold(
+            (0.0, 0.0, 0.0, 0.0),
+            |(mut re, mut im, mut re_n, mut im_n), x| {
+                let theta = 2.0 * PI * (m * n) as f64 / DFT_LEN as f64;
+
+                re += x * (theta).cos();
+                im -= x * (theta).sin();
+                re_n += n as f64 * x * (theta).cos();
+                im_n -= n as f64 * x * (theta).sin();
+                n += 1;
+
+                (re, im, re_n, im_n)
+            },
+        );
+
+        let power = re.powi(2) + im.powi(2);
+        result.magnitude_db.push([f, 20.0 * (power.sqrt()).log10()]);
+        result.phase_rad.push([f, im.atan2(re)]);
+        result
+            .group_delay
+            .push([f, (re_n * re + im_n * im) / power]);
+    }
+
+    result
+}
+
+/// Returns the coherent gain `$\frac{1}{N} \sum_n w[n]$` of a window, the factor by which the window
+/// scales the amplitude of a signal at DC.
+pub fn window_coherent_gain(window: &[f64]) -> f64 {
+    if window.is_empty() {
+        return 1.0;
+    }
+
+    window.iter().sum::<f64>() / window.len() as f64
+}
+
+/// Returns the dB magnitude produced by [`plot_dft`] divided by the coherent gain of the window the
+/// signal was multiplied with, compensating the loss of amplitude caused by windowing.
+///
+/// The correction is exact for signals that are constant over the window. For a windowed sinc the
+/// taps near the center dominate and are barely attenuated by the window, so the corrected DC gain
+/// of a narrow band lowpass comes out above 0 dB.
+pub fn apply_window_correction(magnitude_db: &[[f64; 2]], coherent_gain: f64) -> Vec<[f64; 2]> {
+    let correction_db = 20.0 * coherent_gain.log10();
+
+    magnitude_db
+        .iter()
+        .map(|[f, db]| [*f, db - correction_db])
+        .collect()
+}
+
+/// Returns the magnitude in dB of the filtered signal after keeping every `decimation_factor`-th
+/// sample, on the axis `$[0, f_s / 2M]$` of the decimated signal, from the dB magnitude produced by
+/// [`plot_dft`].
+///
+/// Every frequency of the filter response is folded onto the decimated axis and the powers landing
+/// on the same frequency are summed, so the result shows the aliased stopband on top of the
+/// passband. The gain of `$1/M$` of the decimation is not applied, the passband stays at 0 dB.
+pub fn compute_decimated_spectrum(
+    filter_dft: &[[f64; 2]],
+    decimation_factor: usize,
+) -> Vec<[f64; 2]> {
+    let m = decimation_factor.max(1);
+    if filter_dft.len() < 2 {
+        return filter_dft.to_vec();
+    }
+
+    let df = filter_dft[1][0] - filter_dft[0][0];
+    let f_sampling = df * 2.0 * filter_dft.len() as f64;
+    let f_period = f_sampling / m as f64;
+    let n_points = filter_dft.len().div_ceil(m);
+
+    let mut power = vec![0.0; n_points];
+    for [f, db] in filter_dft {
+        let r = f.rem_euclid(f_period);
+        let f_folded = r.min(f_period - r);
+        let k = ((f_folded / df).round() as usize).min(n_points - 1);
+        power[k] += 10f64.powf(db / 10.0);
+    }
+
+    power
+        .iter()
+        .enumerate()
+        .map(|(k, p)| [k as f64 * df, 10.0 * p.log10()])
+        .collect()
+}
+
+/// Returns the windows reaching `attenuation_db` of stopband attenuation with a transition band of
+/// `transition_width_hz`, paired with their required odd length and sorted by length, shortest
+/// first.
+///
+/// Each length estimated by [`FilterDef::len_for_attenuation`] is checked on a lowpass with its
+/// cutoff at a quarter of the sampling frequency, and grown by 10% until the lowpass meets the
+/// attenuation. Windows still missing it after doubling the length are left out, their side lobes
+/// do not go below the attenuation however long the filter. Parametric windows are not considered.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::data::recommend_window;
+/// use fir_designer::fir::Window;
+///
+/// let recommendations = recommend_window(60.0, 50.0, 1000.0);
+///
+/// assert!(recommendations.windows(2).all(|r| r[0].1 <= r[1].1));
+/// assert!(!recommendations.iter().any(|(window, _)| *window == Window::Rectangular));
+/// ```
+pub fn recommend_window(
+    attenuation_db: f64,
+    transition_width_hz: f64,
+    f_sampling: f64,
+) -> Vec<(Window, usize)> {
+    let meets_attenuation = |window: &Window, len: usize| {
+        let def = FilterDef {
+            window: window.clone(),
+            len,
+            shift: len / 2,
+            f_sampling,
+            f_hi_cut: f_sampling / 4.0,
+            ..Default::default()
+        };
+        let dft = plot_dft(&def.compute_filter(), f_sampling).magnitude_db;
+        def.compute_stopband_attenuation(&dft) >= attenuation_db
+    };
+
+    let mut recommendations: Vec<(Window, usize)> = RECOMMENDED_WINDOWS
+        .iter()
+        .filter_map(|window| {
+            let len = FilterDef::len_for_attenuation(
+                attenuation_db,
+                window,
+                transition_width_hz,
+                f_sampling,
+            );
+            (0..=RECOMMENDATION_GROWTH_STEPS)
+                .map(|k| (len as f64 * 1.1f64.powi(k as i32)).ceil() as usize | 1)
+                .find(|len| meets_attenuation(window, *len))
+                .map(|len| (window.clone(), len))
+        })
+        .collect();
+
+    recommendations.sort_by_key(|(_, len)| *len);
+    recommendations
+}
+
+/// Returns the stopband attenuation in dB, as evaluated by [`FilterDef::compute_all_metrics`], of
+/// the design with `len` centered taps for each of the high cut frequencies, keeping the other
+/// parameters of `def`. One row of the design space spanned by the length and the cutoff.
+pub fn compute_design_space_row(def: &FilterDef, len: usize, f_hi_cuts: &[f64]) -> Vec<f64> {
+    f_hi_cuts
+        .iter()
+        .map(|f_hi_cut| {
+            let def = FilterDef {
+                len,
+                shift: (len.max(1) - 1) / 2,
+                f_hi_cut: *f_hi_cut,
+                ..def.clone()
+            };
+            let dft = plot_dft(&def.compute_filter(), def.f_sampling).magnitude_db;
+            def.compute_stopband_attenuation(&dft)
+        })
+        .collect()
+}
+
+/// Returns the power spectral density `$|H(f)|^2 / (N f_s)$` in units of `$1/\text{Hz}$` from the
+/// dB magnitude produced by [`plot_dft`].
+pub fn plot_psd(dft: &[[f64; 2]], len: usize, f_sampling: f64) -> Vec<[f64; 2]> {
+    dft.iter()
+        .map(|[f, db]| [*f, 10f64.powf(db / 10.0) / (len as f64 * f_sampling)])
+        .collect()
+}
+
+/// Summary statistics of a metric over a set of trials.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Statistics {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+}
+
+impl Statistics {
+    fn from_samples(x: &[f64]) -> Self {
+        if x.is_empty() {
+            return Self::default();
+        }
+
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let variance = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        Self {
+            mean,
+            min: x.iter().copied().fold(f64::INFINITY, f64::min),
+            max: x.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            std_dev: variance.sqrt(),
+        }
+    }
+}
+
+/// Passband ripple and stopband attenuation, both in dB, of a filter with quantized coefficients.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuantizationStats {
+    pub passband_ripple: Statistics,
+    pub stopband_attenuation: Statistics,
+}
+
+/// Deviation of the frequency response caused by quantizing the coefficients, see
+/// [`FilterDef::quantization_analysis_16bit`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuantizationAnalysis {
+    /// Peak level of the response of the rounding errors, relative to unity gain.
+    pub worst_case_magnitude_error_db: f64,
+    /// Largest deviation of the magnitude in dB over the passband.
+    pub worst_case_passband_error_db: f64,
+    /// Stopband attenuation lost to the rounding, negative if the attenuation happens to improve.
+    pub worst_case_stopband_error_db: f64,
+}
+
+/// Returns the spread of the passband ripple and stopband attenuation of the windowed filter when
+/// its coefficients are quantized to `bits` bits, including the sign bit.
+///
+/// Each trial rounds every coefficient to the nearest multiple of `$2^{-bits+1}$` after offsetting
+/// it by a random fraction of that step, so the trials behave like independent quantizers with
+/// uniformly distributed rounding errors. The random sequence is seeded with a constant, making
+/// the results reproducible.
+pub fn monte_carlo_quantization(def: &FilterDef, bits: u8, n_trials: usize) -> QuantizationStats {
+    let f_windowed = def.compute_filter();
+    let step = 2f64.powi(1 - bits as i32);
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    let (passband_ripple, stopband_attenuation): (Vec<f64>, Vec<f64>) = (0..n_trials)
+        .map(|_| {
+            let f_quantized: Vec<f64> = f_windowed
+                .iter()
+                .map(|h| ((h / step + rng.next_f64() - 0.5).round()) * step)
+                .collect();
+            let dft = plot_dft(&f_quantized, def.f_sampling).magnitude_db;
+
+            (
+                def.compute_passband_ripple(&dft),
+                def.compute_stopband_attenuation(&dft),
+            )
+        })
+        .unzip();
+
+    QuantizationStats {
+        passband_ripple: Statistics::from_samples(&passband_ripple),
+        stopband_attenuation: Statistics::from_samples(&stopband_attenuation),
+    }
+}
+
+/// Minimal xorshift pseudo random number generator, good enough for Monte Carlo trials.
+struct XorShift(u64);
+
+impl XorShift {
+    /// Returns a uniformly distributed number in `$[0, 1)$`.
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fir::{Filter, Normalization, WindowMode};
+
+    fn hann_low_pass() -> FilterDef {
+        FilterDef {
+            window: Window::Hann,
+            len: 33,
+            shift: 16,
+            f_sampling: 1000.0,
+            f_hi_cut: 200.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn impulse_response_samples_taps() {
+        let def = hann_low_pass();
+        let f = def.compute_filter();
+        let dt = 1.0 / def.f_sampling;
+        let imp = plot_filter_imp(&f, def.f_sampling);
+
+        assert_eq!(imp.len(), f.len());
+        assert_eq!(imp[0][0], 0.0);
+        for pair in imp.windows(2) {
+            assert!((pair[1][0] - pair[0][0] - dt).abs() < 1e-15);
+        }
+        for ([_, y], h) in imp.iter().zip(&f) {
+            assert_eq!(*y, h * dt);
+        }
+    }
+
+    #[test]
+    fn step_response_ends_at_integral_of_impulse_response() {
+        let def = hann_low_pass();
+        let f = def.compute_filter();
+        let dt = 1.0 / def.f_sampling;
+        let imp = plot_filter_imp(&f, def.f_sampling);
+        let stp = plot_filter_stp(&f, def.f_sampling);
+
+        // The impulse response is scaled by dt, the trapezoidal rule over the taps is exact as the
+        // Hann window is zero at both ends
+        let integral: f64 = imp
+            .windows(2)
+            .map(|pair| (pair[0][1] + pair[1][1]) / 2.0)
+            .sum::<f64>()
+            / dt;
+        let [_, y_end] = stp[stp.len() - 1];
+        assert!((y_end - integral).abs() < 1e-12);
+        assert!((y_end - FilterDef::compute_dc_gain(&f)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hann_window_is_within_unit_range() {
+        for window_mode in [WindowMode::Symmetric, WindowMode::Periodic] {
+            let def = FilterDef {
+                window_mode,
+                ..hann_low_pass()
+            };
+            let w = plot_window(&def.compute_window(), def.f_sampling);
+
+            assert_eq!(w.len(), def.len);
+            assert!(w.iter().all(|[_, y]| (0.0..=1.0).contains(y)));
+        }
+    }
+
+    #[test]
+    fn test_bandpass_zero_bandwidth() {
+        let band_pass = FilterDef {
+            filter: Filter::BandPass,
+            f_lo_cut: 200.0,
+            f_hi_cut: 200.0,
+            ..hann_low_pass()
+        };
+        let band_stop = FilterDef {
+            filter: Filter::BandStop,
+            f_lo_cut: 0.0,
+            f_hi_cut: 200.0,
+            ..hann_low_pass()
+        };
+
+        // The band pass normalizes all zero taps by a zero gain, the band stop has no lower passband
+        // and normalizes by the truncation ripple at DC, blowing the taps up far beyond unity
+        assert!(band_pass.compute_filter().iter().all(|h| h.is_nan()));
+        assert!(band_stop.compute_filter().iter().any(|h| h.abs() > 10.0));
+
+        for def in [band_pass, band_stop] {
+            assert_eq!(def.try_compute_filter(), Err(FilterError::ZeroBandwidth));
+            assert!(matches!(
+                FilterData::try_from(&def),
+                Err(FilterError::ZeroBandwidth)
+            ));
+        }
+    }
+
+    #[test]
+    fn dft_of_impulse_is_flat() {
+        let dft = plot_dft(&[1.0, 0.0, 0.0, 0.0], 1000.0);
+
+        assert_eq!(dft.magnitude_db.len(), DFT_LEN / 2);
+        assert_eq!(dft.phase_rad.len(), dft.magnitude_db.len());
+        assert!(dft.magnitude_db.iter().all(|[_, db]| db.abs() < 1e-12));
+    }
+
+    #[test]
+    fn step_response_is_running_sum_of_taps() {
+        let f = [0.1, -0.2, 0.6, 0.3];
+        let stp = plot_filter_stp(&f, 1000.0);
+
+        let ys: Vec<f64> = stp.iter().map(|[_, y]| *y).collect();
+        assert_eq!(
+            ys,
+            vec![0.1, 0.1 - 0.2, 0.1 - 0.2 + 0.6, 0.1 - 0.2 + 0.6 + 0.3]
+        );
+        assert_eq!(ys[3], FilterDef::compute_dc_gain(&f));
+    }
+
+    #[test]
+    fn step_response_of_low_pass_settles_to_unit_gain() {
+        // The main lobe of the sinc is wider than the filter, so all taps are positive
+        let def = FilterDef {
+            f_hi_cut: 20.0,
+            normalization: Normalization::UnitSum,
+            ..hann_low_pass()
+        };
+        let f = def.compute_filter();
+        assert!(f[1..f.len() - 1].iter().all(|h| *h > 0.0));
+
+        let stp = plot_filter_stp(&f, def.f_sampling);
+        assert!((stp[stp.len() - 1][1] - 1.0).abs() < 1e-12);
+        assert!(stp.windows(2).all(|pair| pair[1][1] >= pair[0][1]));
+    }
+
+    #[test]
+    fn coefficient_range_of_known_taps() {
+        let def = FilterDef {
+            len: 4,
+            shift: 2,
+            f_sampling: 1000.0,
+            f_hi_cut: 200.0,
+            ..Default::default()
+        };
+        let f = [0.25, -0.5, 1.5, 0.125];
+        let metrics = def.compute_all_metrics_of(&f, &plot_dft(&f, def.f_sampling));
+
+        assert_eq!(metrics.max_coefficient, 1.5);
+        assert_eq!(metrics.min_coefficient, -0.5);
+        // 20 log10(1.5 / 0.125) = 20 log10(12)
+        assert!((metrics.dynamic_range_db - 21.583624920952).abs() < 1e-9);
+        // ceil(log2(1.5)) + 1
+        assert_eq!(metrics.required_fractional_bits, 2);
+    }
+
+    #[test]
+    fn window_correction_restores_dc_gain() {
+        // A moving average has unit DC gain, windowing scales it by exactly the coherent gain
+        let f = vec![1.0 / 33.0; 33];
+        for window in [Window::Hann, Window::Blackman, Window::FlatTop] {
+            let w = FilterDef {
+                window,
+                ..hann_low_pass()
+            }
+            .compute_window();
+            let f_windowed = FilterDef::compute_filter_windowed(&f, &w);
+
+            let dft = plot_dft(&f_windowed, 1000.0).magnitude_db;
+            let corrected = apply_window_correction(&dft, window_coherent_gain(&w));
+            assert!(dft[0][1] < -1.0);
+            assert!(corrected[0][1].abs() < 1e-12);
+        }
+    }
+}
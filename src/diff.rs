@@ -0,0 +1,122 @@
+//! Summary of the parameter changes between two filter designs.
+
+use super::fir::{Filter, FilterDef, Normalization, Window, WindowMode};
+use std::fmt;
+
+/// A single changed [`FilterDef`] field holding the `(old, new)` values.
+#[derive(PartialEq, Clone)]
+pub enum FieldChange {
+    Filter(Filter, Filter),
+    Window(Window, Window),
+    WindowMode(WindowMode, WindowMode),
+    Len(usize, usize),
+    Shift(usize, usize),
+    FSampling(f64, f64),
+    FLoCut(f64, f64),
+    FHiCut(f64, f64),
+    ForceSymmetry(bool, bool),
+    Normalization(Normalization, Normalization),
+    DelayOffset(isize, isize),
+}
+
+#[derive(PartialEq, Clone, Default)]
+pub struct FilterDefDiff {
+    pub changed_fields: Vec<FieldChange>,
+}
+
+impl FilterDefDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields.is_empty()
+    }
+}
+
+/// Returns the fields that changed going from `a` to `b`.
+pub fn diff(a: &FilterDef, b: &FilterDef) -> FilterDefDiff {
+    let mut changed_fields = Vec::new();
+
+    if a.filter != b.filter {
+        changed_fields.push(FieldChange::Filter(a.filter.clone(), b.filter.clone()));
+    }
+    if a.window != b.window {
+        changed_fields.push(FieldChange::Window(a.window.clone(), b.window.clone()));
+    }
+    if a.window_mode != b.window_mode {
+        changed_fields.push(FieldChange::WindowMode(a.window_mode, b.window_mode));
+    }
+    if a.len != b.len {
+        changed_fields.push(FieldChange::Len(a.len, b.len));
+    }
+    if a.shift != b.shift {
+        changed_fields.push(FieldChange::Shift(a.shift, b.shift));
+    }
+    if a.f_sampling != b.f_sampling {
+        changed_fields.push(FieldChange::FSampling(a.f_sampling, b.f_sampling));
+    }
+    if a.f_lo_cut != b.f_lo_cut {
+        changed_fields.push(FieldChange::FLoCut(a.f_lo_cut, b.f_lo_cut));
+    }
+    if a.f_hi_cut != b.f_hi_cut {
+        changed_fields.push(FieldChange::FHiCut(a.f_hi_cut, b.f_hi_cut));
+    }
+    if a.force_symmetry != b.force_symmetry {
+        changed_fields.push(FieldChange::ForceSymmetry(
+            a.force_symmetry,
+            b.force_symmetry,
+        ));
+    }
+    if a.normalization != b.normalization {
+        changed_fields.push(FieldChange::Normalization(a.normalization, b.normalization));
+    }
+    if a.delay_offset != b.delay_offset {
+        changed_fields.push(FieldChange::DelayOffset(a.delay_offset, b.delay_offset));
+    }
+
+    FilterDefDiff { changed_fields }
+}
+
+fn format_window(window: &Window) -> String {
+    match window {
+        Window::Planck { epsilon } => format!("Planck (ε = {})", epsilon),
+        Window::Dpss { half_bandwidth } => format!("DPSS (W = {})", half_bandwidth),
+        window => format!("{}", window),
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Filter(Filter::Multiband { .. }, Filter::Multiband { .. }) => {
+                write!(f, "multiband bands")
+            }
+            Self::Filter(Filter::Custom { .. }, Filter::Custom { .. }) => {
+                write!(f, "custom expression")
+            }
+            Self::Filter(a, b) => write!(f, "filter {} → {}", a, b),
+            Self::Window(a, b) => write!(f, "window {} → {}", format_window(a), format_window(b)),
+            Self::WindowMode(a, b) => write!(f, "window_mode {} → {}", a, b),
+            Self::Len(a, b) => write!(f, "len {} → {}", a, b),
+            Self::Shift(a, b) => write!(f, "shift {} → {}", a, b),
+            Self::FSampling(a, b) => write!(f, "f_sampling {} → {} Hz", a, b),
+            Self::FLoCut(a, b) => write!(f, "f_lo_cut {} → {} Hz", a, b),
+            Self::FHiCut(a, b) => write!(f, "f_hi_cut {} → {} Hz", a, b),
+            Self::ForceSymmetry(a, b) => write!(f, "force_symmetry {} → {}", a, b),
+            Self::Normalization(a, b) => write!(f, "normalization {} → {}", a, b),
+            Self::DelayOffset(a, b) => write!(f, "delay_offset {} → {}", a, b),
+        }
+    }
+}
+
+impl fmt::Display for FilterDefDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No changes");
+        }
+
+        let changes: Vec<String> = self
+            .changed_fields
+            .iter()
+            .map(|change| format!("{}", change))
+            .collect();
+        write!(f, "Changed: {}", changes.join(", "))
+    }
+}
@@ -0,0 +1,444 @@
+use super::data::{FilterData, FilterMetrics};
+use super::fir::{FilterDef, FreqPoint, ParseError};
+use serde_json::json;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Largest absolute difference allowed between embedded and recomputed coefficients.
+const COEFFICIENT_TOLERANCE: f64 = 1e-12;
+/// Line width the generated Rust source is wrapped at.
+const RUST_LINE_WIDTH: usize = 80;
+/// Longest array element rustfmt still packs several of onto one line.
+const RUST_SHORT_ELEMENT_WIDTH: usize = 10;
+
+/// Floating point type of exported coefficients.
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub enum Precision {
+    #[default]
+    F32,
+    F64,
+}
+
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::F32 => write!(f, "f32"),
+            Self::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+/// Returns the filter coefficients as a LabVIEW 1D array literal `{h0, h1, h2, ...}`, preceded by a
+/// comment block listing the design parameters.
+pub fn to_labview_array_string(f: &[f64], def: &FilterDef) -> String {
+    let values: Vec<String> = f.iter().map(|h| format!("{}", h)).collect();
+
+    format!("{}{{{}}}\n", design_comment(def, "//"), values.join(", "))
+}
+
+/// Returns the coefficients as a Rust `const` array named `name` in upper case, of `f32` or `f64`
+/// values depending on `precision`, laid out the way rustfmt formats it within 80 columns.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::export::{to_rust_const_array, Precision};
+///
+/// assert_eq!(
+///     to_rust_const_array(&[0.25, 0.5, 0.25], "taps", Precision::F32),
+///     "#[allow(clippy::excessive_precision)]\nconst TAPS: [f32; 3] = [0.25, 0.5, 0.25];\n"
+/// );
+/// ```
+pub fn to_rust_const_array(coefficients: &[f64], name: &str, precision: Precision) -> String {
+    let values: Vec<String> = coefficients
+        .iter()
+        .map(|h| match precision {
+            Precision::F32 => format!("{:?}", *h as f32),
+            Precision::F64 => format!("{:?}", h),
+        })
+        .collect();
+    let declaration = format!(
+        "const {}: [{}; {}] = ",
+        name.to_uppercase(),
+        precision,
+        values.len()
+    );
+    let mut array = String::from("#[allow(clippy::excessive_precision)]\n");
+
+    let single_line = format!("{}[{}];", declaration, values.join(", "));
+    if single_line.len() <= RUST_LINE_WIDTH {
+        array.push_str(&single_line);
+        array.push('\n');
+        return array;
+    }
+
+    array.push_str(&declaration);
+    array.push_str("[\n");
+    if values.iter().all(|v| v.len() <= RUST_SHORT_ELEMENT_WIDTH) {
+        // Short literals are packed into as many columns as fit
+        let mut line = String::new();
+        for value in &values {
+            if !line.is_empty() && line.len() + value.len() + 2 >= RUST_LINE_WIDTH {
+                array.push_str(line.trim_end());
+                array.push('\n');
+                line.clear();
+            }
+            if line.is_empty() {
+                line.push_str("   ");
+            }
+            line.push_str(&format!(" {},", value));
+        }
+        array.push_str(&line);
+        array.push('\n');
+    } else {
+        for value in &values {
+            array.push_str(&format!("    {},\n", value));
+        }
+    }
+    array.push_str("];\n");
+    array
+}
+
+/// Returns a JSON archive of the design containing the filter parameters, the windowed coefficients,
+/// the DFT magnitude in dB, the design method, the app version and a Unix timestamp in seconds.
+pub fn to_json_full(def: &FilterDef, data: &FilterData, app_version: &str) -> serde_json::Value {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.as_secs())
+        .unwrap_or_default();
+
+    json!({
+        "method": "windowed-sinc",
+        "version": app_version,
+        "timestamp": timestamp,
+        "filter": def,
+        "coefficients": data.f_windowed,
+        "dft": data.f_windowed_dft.magnitude_db,
+    })
+}
+
+/// Reconstructs the [`FilterDef`] from a JSON archive produced by [`to_json_full`].
+///
+/// If `verify` is set the embedded coefficients are compared against a freshly computed filter and
+/// an error is returned if they differ.
+pub fn from_json_full(json: &serde_json::Value, verify: bool) -> Result<FilterDef, ParseError> {
+    let def: FilterDef = serde_json::from_value(json["filter"].clone())
+        .map_err(|e| ParseError(format!("invalid filter: {}", e)))?;
+
+    if verify {
+        let coefficients: Vec<f64> = serde_json::from_value(json["coefficients"].clone())
+            .map_err(|e| ParseError(format!("invalid coefficients: {}", e)))?;
+        let expected = def.compute_filter();
+
+        let matches = coefficients.len() == expected.len()
+            && coefficients
+                .iter()
+                .zip(&expected)
+                .all(|(a, b)| (a - b).abs() <= COEFFICIENT_TOLERANCE);
+        if !matches {
+            return Err(ParseError(
+                "embedded coefficients do not match the filter definition".to_string(),
+            ));
+        }
+    }
+
+    Ok(def)
+}
+
+/// Parses a frequency response from a two column CSV of `Hz, dB` pairs.
+///
+/// Empty lines and lines starting with `#` are skipped, as is a first line that does not parse as
+/// numbers, which is taken to be a header.
+pub fn from_csv_response(csv: &str) -> Result<Vec<[f64; 2]>, ParseError> {
+    let mut response = Vec::new();
+
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values: Vec<Result<f64, _>> = line.split(',').map(|v| v.trim().parse()).collect();
+        match values.as_slice() {
+            [Ok(f), Ok(db)] => response.push([*f, *db]),
+            _ if i == 0 => continue,
+            _ => {
+                return Err(ParseError(format!(
+                    "line {}: expected 'Hz, dB', found '{}'",
+                    i + 1,
+                    line
+                )))
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+/// Returns the coefficients as JSON Lines, one `{"index": n, "value": h[n]}` object per line.
+///
+/// Every line is a complete JSON document, so the coefficients can be streamed and parsed one line
+/// at a time.
+pub fn to_jsonlines(coefficients: &[f64]) -> String {
+    coefficients
+        .iter()
+        .enumerate()
+        .map(|(index, value)| format!("{}\n", json!({ "index": index, "value": value })))
+        .collect()
+}
+
+/// Parses coefficients from JSON Lines produced by [`to_jsonlines`].
+///
+/// Empty lines are skipped and the lines may arrive in any order, but every index from 0 to the
+/// number of coefficients must appear exactly once.
+pub fn from_jsonlines(s: &str) -> Result<Vec<f64>, ParseError> {
+    let mut coefficients: Vec<Option<f64>> = Vec::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |msg: &str| ParseError(format!("line {}: {}", i + 1, msg));
+        let object: serde_json::Value =
+            serde_json::from_str(line).map_err(|e| error(&e.to_string()))?;
+        let index = object["index"]
+            .as_u64()
+            .ok_or_else(|| error("missing or invalid 'index'"))? as usize;
+        let value = object["value"]
+            .as_f64()
+            .ok_or_else(|| error("missing or invalid 'value'"))?;
+
+        if index >= coefficients.len() {
+            coefficients.resize(index + 1, None);
+        }
+        if coefficients[index].replace(value).is_some() {
+            return Err(error(&format!("duplicate index {}", index)));
+        }
+    }
+
+    coefficients
+        .iter()
+        .enumerate()
+        .map(|(index, value)| value.ok_or_else(|| ParseError(format!("missing index {}", index))))
+        .collect()
+}
+
+/// Parses a signal from a two column CSV of sample index and value, as written by [`to_csv`].
+///
+/// Empty lines and lines starting with `#` are skipped, as is a first line that does not parse as
+/// numbers, which is taken to be a header. The sample index column is not checked.
+pub fn from_csv(csv: &str) -> Result<Vec<f64>, ParseError> {
+    let mut signal = Vec::new();
+
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values: Vec<Result<f64, _>> = line.split(',').map(|v| v.trim().parse()).collect();
+        match values.as_slice() {
+            [Ok(_), Ok(x)] => signal.push(*x),
+            _ if i == 0 => continue,
+            _ => {
+                return Err(ParseError(format!(
+                    "line {}: expected 'n, value', found '{}'",
+                    i + 1,
+                    line
+                )))
+            }
+        }
+    }
+
+    Ok(signal)
+}
+
+/// Returns a signal as a two column CSV of sample index and value, with `name` as the header of the
+/// value column.
+pub fn to_csv(signal: &[f64], name: &str) -> String {
+    let mut csv = format!("n,{}\n", name);
+    for (n, x) in signal.iter().enumerate() {
+        csv.push_str(&format!("{},{}\n", n, x));
+    }
+    csv
+}
+
+/// Returns a frequency response table as a four column CSV with the header
+/// `freq_hz,magnitude_db,phase_rad,group_delay_samples`.
+pub fn to_frequency_response_csv(table: &[FreqPoint]) -> String {
+    let mut csv = String::from("freq_hz,magnitude_db,phase_rad,group_delay_samples\n");
+    for point in table {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            point.freq_hz, point.magnitude_db, point.phase_rad, point.group_delay_samples
+        ));
+    }
+    csv
+}
+
+/// Returns the design parameters and the main metrics as a GitHub Flavored Markdown table with the
+/// columns Parameter, Value and Unit.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::export::to_markdown_table;
+/// use fir_designer::fir::FilterDef;
+///
+/// let def = FilterDef {
+///     len: 65,
+///     shift: 32,
+///     f_sampling: 1000.0,
+///     f_hi_cut: 300.0,
+///     ..Default::default()
+/// };
+/// let table = to_markdown_table(&def, &def.compute_all_metrics());
+///
+/// let mut lines = table.lines();
+/// assert_eq!(lines.next(), Some("| Parameter | Value | Unit |"));
+/// assert_eq!(lines.next(), Some("| :-- | --: | :-- |"));
+/// assert!(lines.all(|line| line.starts_with('|') && line.matches('|').count() == 4));
+/// ```
+pub fn to_markdown_table(def: &FilterDef, metrics: &FilterMetrics) -> String {
+    let mut table = String::from("| Parameter | Value | Unit |\n| :-- | --: | :-- |\n");
+    for (parameter, value, unit) in summary_rows(def, metrics) {
+        table.push_str(&format!("| {} | {} | {} |\n", parameter, value, unit));
+    }
+    table
+}
+
+/// Returns the design parameters and main metrics as a plain ASCII table for console output.
+///
+/// ```
+/// use fir_designer::export::format_design_summary;
+/// use fir_designer::fir::FilterDef;
+///
+/// let def = FilterDef {
+///     len: 65,
+///     shift: 32,
+///     f_sampling: 1000.0,
+///     f_hi_cut: 300.0,
+///     ..Default::default()
+/// };
+/// let summary = format_design_summary(&def, &def.compute_all_metrics());
+///
+/// assert!(summary.lines().any(|line| line.starts_with("| Length ")));
+/// assert!(summary.lines().all(|line| line.len() == summary.lines().next().unwrap().len()));
+/// ```
+pub fn format_design_summary(def: &FilterDef, metrics: &FilterMetrics) -> String {
+    let rows = summary_rows(def, metrics);
+    let header = ("Parameter", "Value".to_string(), "Unit");
+    let width = |column: fn(&(&str, String, &str)) -> usize| {
+        rows.iter().chain([&header]).map(column).max().unwrap_or(0)
+    };
+    let widths = [
+        width(|row| row.0.chars().count()),
+        width(|row| row.1.chars().count()),
+        width(|row| row.2.chars().count()),
+    ];
+
+    let separator = format!(
+        "+-{}-+-{}-+-{}-+\n",
+        "-".repeat(widths[0]),
+        "-".repeat(widths[1]),
+        "-".repeat(widths[2])
+    );
+    let format_row = |(parameter, value, unit): &(&str, String, &str)| {
+        format!(
+            "| {:<w0$} | {:>w1$} | {:<w2$} |\n",
+            parameter,
+            value,
+            unit,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )
+    };
+
+    let mut summary = separator.clone();
+    summary.push_str(&format_row(&header));
+    summary.push_str(&separator);
+    for row in &rows {
+        summary.push_str(&format_row(row));
+    }
+    summary.push_str(&separator);
+    summary
+}
+
+/// Rows of `(parameter, value, unit)` shared by the design summaries.
+fn summary_rows(
+    def: &FilterDef,
+    metrics: &FilterMetrics,
+) -> [(&'static str, String, &'static str); 11] {
+    [
+        ("Filter Type", format!("{}", def.filter), ""),
+        ("Window", format!("{}", def.window), ""),
+        ("Length", format!("{}", def.len), "samples"),
+        ("Shift", format!("{}", def.shift), "samples"),
+        ("Sampling Rate", format!("{}", def.f_sampling), "Hz"),
+        ("Low Cut", format!("{}", def.f_lo_cut), "Hz"),
+        ("High Cut", format!("{}", def.f_hi_cut), "Hz"),
+        (
+            "Passband Ripple",
+            format!("{:.2}", metrics.passband_ripple_db),
+            "dB",
+        ),
+        (
+            "Stopband Attenuation",
+            format!("{:.1}", metrics.stopband_attenuation_db),
+            "dB",
+        ),
+        (
+            "Group Delay",
+            format!("{:.2}", metrics.group_delay_samples),
+            "samples",
+        ),
+        (
+            "ENBW",
+            format!("{:.1}", metrics.equivalent_noise_bandwidth_hz),
+            "Hz",
+        ),
+    ]
+}
+
+/// Returns the design parameters of a filter as comment lines starting with `prefix`.
+fn design_comment(def: &FilterDef, prefix: &str) -> String {
+    format!(
+        "{prefix} FIR filter: {}, {} window\n\
+         {prefix} Length: {} samples, Shift: {} samples\n\
+         {prefix} Sampling Frequency: {} Hz, Low Cut: {} Hz, High Cut: {} Hz\n",
+        def.filter, def.window, def.len, def.shift, def.f_sampling, def.f_lo_cut, def.f_hi_cut,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jsonlines_round_trip() {
+        let coefficients = FilterDef {
+            len: 64,
+            shift: 32,
+            f_sampling: 1000.0,
+            f_hi_cut: 123.0,
+            ..Default::default()
+        }
+        .compute_filter();
+        let s = to_jsonlines(&coefficients);
+
+        assert_eq!(s.lines().count(), 64);
+        for (n, line) in s.lines().enumerate() {
+            let object: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(object["index"], n);
+            assert_eq!(object["value"], coefficients[n]);
+        }
+        assert_eq!(from_jsonlines(&s), Ok(coefficients.clone()));
+
+        // Lines may arrive in any order
+        let reversed: String = s.lines().rev().map(|line| format!("{}\n", line)).collect();
+        assert_eq!(from_jsonlines(&reversed), Ok(coefficients));
+    }
+}
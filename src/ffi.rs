@@ -0,0 +1,237 @@
+//! C compatible interface to the filter design kernel for use from C, C++, Python (ctypes) or
+//! MATLAB (MEX).
+//!
+//! The header `include/fir_designer.h` is generated with
+//!
+//! ```text
+//! cbindgen --config cbindgen.toml --output include/fir_designer.h
+//! ```
+//!
+//! Every array returned by this interface is heap allocated by Rust and must be released with
+//! [`fir_free`].
+
+use super::fir::{Filter, FilterDef, Window, DFT_LEN};
+use std::os::raw::c_int;
+use std::ptr;
+
+pub const FIR_WINDOW_RECTANGULAR: c_int = 0;
+pub const FIR_WINDOW_TRIANGULAR: c_int = 1;
+pub const FIR_WINDOW_WELCH: c_int = 2;
+pub const FIR_WINDOW_SIN: c_int = 3;
+pub const FIR_WINDOW_HANN: c_int = 4;
+pub const FIR_WINDOW_HAMMING: c_int = 5;
+pub const FIR_WINDOW_BLACKMAN: c_int = 6;
+pub const FIR_WINDOW_NUTTALL: c_int = 7;
+pub const FIR_WINDOW_BLACKMAN_NUTTALL: c_int = 8;
+pub const FIR_WINDOW_BLACKMAN_HARRIS: c_int = 9;
+pub const FIR_WINDOW_FLAT_TOP: c_int = 10;
+
+fn window_from_c(window_type: c_int) -> Option<Window> {
+    match window_type {
+        FIR_WINDOW_RECTANGULAR => Some(Window::Rectangular),
+        FIR_WINDOW_TRIANGULAR => Some(Window::Triangular),
+        FIR_WINDOW_WELCH => Some(Window::Welch),
+        FIR_WINDOW_SIN => Some(Window::Sin),
+        FIR_WINDOW_HANN => Some(Window::Hann),
+        FIR_WINDOW_HAMMING => Some(Window::Hamming),
+        FIR_WINDOW_BLACKMAN => Some(Window::Blackman),
+        FIR_WINDOW_NUTTALL => Some(Window::Nuttall),
+        FIR_WINDOW_BLACKMAN_NUTTALL => Some(Window::BlackmanNuttall),
+        FIR_WINDOW_BLACKMAN_HARRIS => Some(Window::BlackmanHarris),
+        FIR_WINDOW_FLAT_TOP => Some(Window::FlatTop),
+        _ => None,
+    }
+}
+
+/// Hands ownership of the array to the caller, writing its length to `out_len`.
+unsafe fn into_raw(v: Vec<f64>, out_len: *mut usize) -> *mut f64 {
+    let v = v.into_boxed_slice();
+    *out_len = v.len();
+
+    Box::into_raw(v) as *mut f64
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe fn design(
+    filter: Filter,
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    len: usize,
+    shift: usize,
+    window_type: c_int,
+    out_len: *mut usize,
+) -> *mut f64 {
+    let window = match window_from_c(window_type) {
+        Some(window) if !out_len.is_null() => window,
+        _ => return ptr::null_mut(),
+    };
+
+    let def = FilterDef {
+        filter,
+        window,
+        len,
+        shift,
+        f_sampling,
+        f_lo_cut,
+        f_hi_cut,
+        ..Default::default()
+    };
+
+    match def.try_compute_filter() {
+        Ok(f) => into_raw(f, out_len),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Designs a lowpass filter, returning the coefficients and writing their count to `out_len`.
+///
+/// Returns null if `window_type` is not one of the `FIR_WINDOW_*` constants or the parameters
+/// cannot produce a filter, e.g. a bandpass with equal cutoffs or a bandstop with `f_lo_cut` at DC.
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fir_design_lowpass(
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    len: usize,
+    shift: usize,
+    window_type: c_int,
+    out_len: *mut usize,
+) -> *mut f64 {
+    design(
+        Filter::LowPass,
+        f_sampling,
+        f_lo_cut,
+        f_hi_cut,
+        len,
+        shift,
+        window_type,
+        out_len,
+    )
+}
+
+/// Designs a highpass filter, see [`fir_design_lowpass`].
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fir_design_highpass(
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    len: usize,
+    shift: usize,
+    window_type: c_int,
+    out_len: *mut usize,
+) -> *mut f64 {
+    design(
+        Filter::HighPass,
+        f_sampling,
+        f_lo_cut,
+        f_hi_cut,
+        len,
+        shift,
+        window_type,
+        out_len,
+    )
+}
+
+/// Designs a bandpass filter, see [`fir_design_lowpass`].
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fir_design_bandpass(
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    len: usize,
+    shift: usize,
+    window_type: c_int,
+    out_len: *mut usize,
+) -> *mut f64 {
+    design(
+        Filter::BandPass,
+        f_sampling,
+        f_lo_cut,
+        f_hi_cut,
+        len,
+        shift,
+        window_type,
+        out_len,
+    )
+}
+
+/// Designs a bandstop filter, see [`fir_design_lowpass`].
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fir_design_bandstop(
+    f_sampling: f64,
+    f_lo_cut: f64,
+    f_hi_cut: f64,
+    len: usize,
+    shift: usize,
+    window_type: c_int,
+    out_len: *mut usize,
+) -> *mut f64 {
+    design(
+        Filter::BandStop,
+        f_sampling,
+        f_lo_cut,
+        f_hi_cut,
+        len,
+        shift,
+        window_type,
+        out_len,
+    )
+}
+
+/// Filters `signal` with the coefficients, returning an output of the same length as the signal.
+///
+/// Returns null if any of the pointers are null.
+///
+/// # Safety
+///
+/// `coefficients` and `signal` must point to `len` and `signal_len` readable values and `out_len`
+/// must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fir_apply(
+    coefficients: *const f64,
+    len: usize,
+    signal: *const f64,
+    signal_len: usize,
+    out_len: *mut usize,
+) -> *mut f64 {
+    if coefficients.is_null() || signal.is_null() || out_len.is_null() {
+        return ptr::null_mut();
+    }
+
+    let f = std::slice::from_raw_parts(coefficients, len);
+    let signal = std::slice::from_raw_parts(signal, signal_len);
+
+    into_raw(FilterDef::apply_overlap_add(f, signal, DFT_LEN), out_len)
+}
+
+/// Releases an array returned by one of the design functions or [`fir_apply`].
+///
+/// # Safety
+///
+/// `ptr` must be null or an array returned by this interface that has not been freed yet, and `len`
+/// must be the length written to `out_len` when it was returned.
+#[no_mangle]
+pub unsafe extern "C" fn fir_free(ptr: *mut f64, len: usize) {
+    if ptr.is_null() {
+        return;
+    }
+
+    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)));
+}
@@ -0,0 +1,433 @@
+//! Compact, human readable representation of a [`FilterDef`] for sharing designs as plain text.
+//!
+//! The format is
+//!
+//! ```text
+//! <filter>-<window>-<len>-<shift>-<cuts>@<f_sampling>[-periodic][-sym][-energy|-sum][-delay=<taps>]
+//! ```
+//!
+//! - `<filter>` is one of `LP`, `HP`, `BP`, `BS`, `MB` for multiband filters or `CU` for custom
+//!   kernels. [`Filter::Kernel`] designs are written as `KE` for display, they cannot be parsed
+//!   back.
+//! - `<window>` is the window name without spaces, e.g. `Hamming` or `BlackmanHarris`. Parametric
+//!   windows carry their parameter in parentheses, e.g. `Planck(0.1)` or `DPSS(0.05)`. Modified
+//!   windows are written as `<window>^2`, `<window>^3`, `sqrt(<window>)` or
+//!   `conv(<window>,<window>)`.
+//! - `<len>` and `<shift>` are the filter length and shift in samples.
+//! - `<cuts>` is a comma separated list of `f_lo=<freq>` and `f_hi=<freq>`. Only the cut frequencies
+//!   used by the filter type are written, plus any unused cut frequency that is non-zero. Multiband
+//!   filters add their bands in order as `pass=<freq>:<freq>` or `stop=<freq>:<freq>`. Custom
+//!   kernels end the list with `expr=<expression>`, which extends to the `@`.
+//! - `<freq>` and `<f_sampling>` are numbers followed by one of the units `Hz`, `kHz`, `MHz` or `GHz`.
+//! - The optional `-periodic` suffix selects [`WindowMode::Periodic`].
+//! - The optional `-sym` suffix enables [`FilterDef::force_symmetry`].
+//! - The optional `-energy` and `-sum` suffixes select [`Normalization::UnitEnergy`] and
+//!   [`Normalization::UnitSum`].
+//! - The optional `-delay=<taps>` suffix sets a non-zero [`FilterDef::delay_offset`].
+//!
+//! For example `LP-Hamming-64-32-f_hi=300Hz@1kHz`.
+
+use super::fir::{
+    Filter, FilterDef, Normalization, ParseError, Window, WindowMode, WindowModifier,
+};
+
+const UNITS: [(&str, f64); 4] = [("GHz", 1e9), ("MHz", 1e6), ("kHz", 1e3), ("Hz", 1.0)];
+
+impl FilterDef {
+    /// Returns the compact string representation of the filter, see the [module](self) docs.
+    pub fn to_filter_string(&self) -> String {
+        let (uses_lo, uses_hi) = match self.filter {
+            Filter::LowPass => (false, true),
+            Filter::HighPass => (true, false),
+            Filter::BandPass | Filter::BandStop | Filter::Custom { .. } | Filter::Kernel(_) => {
+                (true, true)
+            }
+            Filter::Multiband { .. } => (false, false),
+        };
+        let mut cuts = Vec::new();
+        if uses_lo || self.f_lo_cut != 0.0 {
+            cuts.push(format!("f_lo={}", format_freq(self.f_lo_cut)));
+        }
+        if uses_hi || self.f_hi_cut != 0.0 {
+            cuts.push(format!("f_hi={}", format_freq(self.f_hi_cut)));
+        }
+        if let Filter::Multiband { bands } = &self.filter {
+            for (f_lo, f_hi, is_passband) in bands {
+                let kind = if *is_passband { "pass" } else { "stop" };
+                cuts.push(format!(
+                    "{}={}:{}",
+                    kind,
+                    format_freq(*f_lo),
+                    format_freq(*f_hi)
+                ));
+            }
+        }
+        if let Filter::Custom { expression } = &self.filter {
+            cuts.push(format!("expr={}", expression));
+        }
+
+        let mut s = format!(
+            "{}-{}-{}-{}-{}@{}",
+            filter_code(&self.filter),
+            format_window(&self.window),
+            self.len,
+            self.shift,
+            cuts.join(","),
+            format_freq(self.f_sampling)
+        );
+        if self.window_mode == WindowMode::Periodic {
+            s.push_str("-periodic");
+        }
+        if self.force_symmetry {
+            s.push_str("-sym");
+        }
+        match self.normalization {
+            Normalization::UnitPassbandGain => (),
+            Normalization::UnitEnergy => s.push_str("-energy"),
+            Normalization::UnitSum => s.push_str("-sum"),
+        }
+        if self.delay_offset != 0 {
+            s.push_str(&format!("-delay={}", self.delay_offset));
+        }
+        s
+    }
+
+    /// Returns an abbreviated description of the filter for lists of designs, e.g.
+    /// `LP-Hamming-64 @ 1kHz`. Unlike [`FilterDef::to_filter_string`] it cannot be parsed back.
+    pub fn to_short_filter_string(&self) -> String {
+        format!(
+            "{}-{}-{} @ {}",
+            filter_code(&self.filter),
+            format_window(&self.window),
+            self.len,
+            format_freq(self.f_sampling)
+        )
+    }
+
+    /// Parses a filter from its compact string representation, see the [module](self) docs.
+    pub fn from_filter_string(s: &str) -> Result<FilterDef, ParseError> {
+        let s = s.trim();
+        let (s, delay_offset) = match s.rsplit_once("-delay=") {
+            Some((s, delay_offset)) => (s, parse_number(delay_offset, "delay offset")?),
+            None => (s, 0),
+        };
+        let (s, normalization) = if let Some(s) = s.strip_suffix("-energy") {
+            (s, Normalization::UnitEnergy)
+        } else if let Some(s) = s.strip_suffix("-sum") {
+            (s, Normalization::UnitSum)
+        } else {
+            (s, Normalization::UnitPassbandGain)
+        };
+        let (s, force_symmetry) = match s.strip_suffix("-sym") {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+        let (s, window_mode) = match s.strip_suffix("-periodic") {
+            Some(s) => (s, WindowMode::Periodic),
+            None => (s, WindowMode::Symmetric),
+        };
+
+        let (body, f_sampling) = s
+            .rsplit_once('@')
+            .ok_or_else(|| ParseError("missing '@<f_sampling>'".to_string()))?;
+        let parts: Vec<&str> = body.splitn(5, '-').collect();
+        if parts.len() != 5 {
+            return Err(ParseError(format!(
+                "expected '<filter>-<window>-<len>-<shift>-<cuts>', found '{}'",
+                body
+            )));
+        }
+
+        let filter = match parts[0] {
+            "LP" => Filter::LowPass,
+            "HP" => Filter::HighPass,
+            "BP" => Filter::BandPass,
+            "BS" => Filter::BandStop,
+            "MB" => Filter::Multiband { bands: Vec::new() },
+            "CU" => Filter::Custom {
+                expression: String::new(),
+            },
+            "KE" => {
+                return Err(ParseError(
+                    "kernel filters cannot be parsed from a filter string".to_string(),
+                ))
+            }
+            other => return Err(ParseError(format!("unknown filter type '{}'", other))),
+        };
+
+        let mut def = FilterDef {
+            filter,
+            window: parse_window(parts[1])?,
+            window_mode,
+            len: parse_number(parts[2], "length")?,
+            shift: parse_number(parts[3], "shift")?,
+            f_sampling: parse_freq(f_sampling)?,
+            force_symmetry,
+            normalization,
+            delay_offset,
+            ..Default::default()
+        };
+
+        let (cuts, expression) = match parts[4].split_once("expr=") {
+            Some((cuts, expression)) => (cuts, Some(expression)),
+            None => (parts[4], None),
+        };
+        match (&mut def.filter, expression) {
+            (Filter::Custom { expression }, Some(e)) => *expression = e.to_string(),
+            (Filter::Custom { .. }, None) => {
+                return Err(ParseError("missing 'expr=<expression>'".to_string()))
+            }
+            (_, Some(_)) => {
+                return Err(ParseError("expression on a non custom filter".to_string()))
+            }
+            (_, None) => (),
+        }
+
+        for cut in cuts.split(',').filter(|cut| !cut.is_empty()) {
+            match cut.split_once('=') {
+                Some(("f_lo", freq)) => def.f_lo_cut = parse_freq(freq)?,
+                Some(("f_hi", freq)) => def.f_hi_cut = parse_freq(freq)?,
+                Some((kind @ ("pass" | "stop"), band)) => {
+                    let Filter::Multiband { bands } = &mut def.filter else {
+                        return Err(ParseError(format!(
+                            "band '{}' on a non multiband filter",
+                            cut
+                        )));
+                    };
+                    let (f_lo, f_hi) = band
+                        .split_once(':')
+                        .ok_or_else(|| ParseError(format!("invalid band '{}'", cut)))?;
+                    bands.push((parse_freq(f_lo)?, parse_freq(f_hi)?, kind == "pass"));
+                }
+                _ => return Err(ParseError(format!("invalid cut frequency '{}'", cut))),
+            }
+        }
+
+        Ok(def)
+    }
+}
+
+fn filter_code(filter: &Filter) -> &'static str {
+    match filter {
+        Filter::LowPass => "LP",
+        Filter::HighPass => "HP",
+        Filter::BandPass => "BP",
+        Filter::BandStop => "BS",
+        Filter::Multiband { .. } => "MB",
+        Filter::Custom { .. } => "CU",
+        Filter::Kernel(_) => "KE",
+    }
+}
+
+fn format_window(window: &Window) -> String {
+    match window {
+        Window::Planck { epsilon } => format!("Planck({})", epsilon),
+        Window::Dpss { half_bandwidth } => format!("DPSS({})", half_bandwidth),
+        Window::Modified { base, modifier } => match modifier {
+            WindowModifier::Squared => format!("{}^2", format_window(base)),
+            WindowModifier::Sqrt => format!("sqrt({})", format_window(base)),
+            WindowModifier::Cube => format!("{}^3", format_window(base)),
+            WindowModifier::Convolved(other) => {
+                format!("conv({},{})", format_window(base), format_window(other))
+            }
+        },
+        window => format!("{}", window).replace(' ', ""),
+    }
+}
+
+fn parse_window(s: &str) -> Result<Window, ParseError> {
+    let modified = |base: &str, modifier| -> Result<Window, ParseError> {
+        Ok(Window::Modified {
+            base: Box::new(parse_window(base)?),
+            modifier,
+        })
+    };
+    if let Some(base) = s.strip_suffix("^2") {
+        return modified(base, WindowModifier::Squared);
+    }
+    if let Some(base) = s.strip_suffix("^3") {
+        return modified(base, WindowModifier::Cube);
+    }
+    if let Some(base) = s.strip_prefix("sqrt(").and_then(|s| s.strip_suffix(')')) {
+        return modified(base, WindowModifier::Sqrt);
+    }
+    if let Some(windows) = s.strip_prefix("conv(").and_then(|s| s.strip_suffix(')')) {
+        // Split at the comma outside of any nested parentheses
+        let mut depth = 0;
+        let split = windows.find(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => (),
+            }
+            c == ',' && depth == 0
+        });
+        let Some(split) = split else {
+            return Err(ParseError(format!("expected two windows in '{}'", s)));
+        };
+        let other = parse_window(&windows[split + 1..])?;
+        return modified(
+            &windows[..split],
+            WindowModifier::Convolved(Box::new(other)),
+        );
+    }
+
+    if let Some(epsilon) = s.strip_prefix("Planck(").and_then(|s| s.strip_suffix(')')) {
+        return Ok(Window::Planck {
+            epsilon: parse_number(epsilon, "epsilon")?,
+        });
+    }
+
+    if let Some(half_bandwidth) = s.strip_prefix("DPSS(").and_then(|s| s.strip_suffix(')')) {
+        return Ok(Window::Dpss {
+            half_bandwidth: parse_number(half_bandwidth, "half bandwidth")?,
+        });
+    }
+
+    match s {
+        "Rectangular" => Ok(Window::Rectangular),
+        "Triangular" => Ok(Window::Triangular),
+        "Welch" => Ok(Window::Welch),
+        "Sin" => Ok(Window::Sin),
+        "Hann" => Ok(Window::Hann),
+        "Hamming" => Ok(Window::Hamming),
+        "Blackman" => Ok(Window::Blackman),
+        "Nuttall" | "Nutall" => Ok(Window::Nuttall),
+        "BlackmanNuttall" | "BlackmanNutall" => Ok(Window::BlackmanNuttall),
+        "BlackmanHarris" => Ok(Window::BlackmanHarris),
+        "FlatTop" => Ok(Window::FlatTop),
+        other => Err(ParseError(format!("unknown window type '{}'", other))),
+    }
+}
+
+/// Formats a frequency with the largest unit that still represents the value exactly.
+fn format_freq(freq: f64) -> String {
+    for (unit, scale) in UNITS {
+        let value = freq / scale;
+        if freq.abs() >= scale && value * scale == freq {
+            return format!("{}{}", value, unit);
+        }
+    }
+
+    format!("{}Hz", freq)
+}
+
+fn parse_freq(s: &str) -> Result<f64, ParseError> {
+    for (unit, scale) in UNITS {
+        if let Some(value) = s.strip_suffix(unit) {
+            return Ok(parse_number::<f64>(value, "frequency")? * scale);
+        }
+    }
+
+    Err(ParseError(format!("missing unit on frequency '{}'", s)))
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str, name: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError(format!("invalid {} '{}'", name, s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_string_round_trips() {
+        let filters = [
+            Filter::LowPass,
+            Filter::HighPass,
+            Filter::BandPass,
+            Filter::BandStop,
+            Filter::Multiband {
+                bands: vec![
+                    (0.0, 100.0, true),
+                    (100.0, 250.5, false),
+                    (250.5, 500.0, true),
+                ],
+            },
+            Filter::Custom {
+                expression: "2 * f_hi_cut * sinc(2 * f_hi_cut * dt * (n - shift))".to_string(),
+            },
+        ];
+        let windows = [
+            Window::Rectangular,
+            Window::Triangular,
+            Window::Welch,
+            Window::Sin,
+            Window::Hann,
+            Window::Hamming,
+            Window::Blackman,
+            Window::Nuttall,
+            Window::BlackmanNuttall,
+            Window::BlackmanHarris,
+            Window::FlatTop,
+            Window::Planck { epsilon: 0.1 },
+            Window::Dpss {
+                half_bandwidth: 0.05,
+            },
+            Window::Modified {
+                base: Box::new(Window::Hann),
+                modifier: WindowModifier::Squared,
+            },
+            Window::Modified {
+                base: Box::new(Window::Planck { epsilon: 0.25 }),
+                modifier: WindowModifier::Sqrt,
+            },
+            Window::Modified {
+                base: Box::new(Window::Blackman),
+                modifier: WindowModifier::Cube,
+            },
+            Window::Modified {
+                base: Box::new(Window::Hamming),
+                modifier: WindowModifier::Convolved(Box::new(Window::Dpss {
+                    half_bandwidth: 0.1,
+                })),
+            },
+        ];
+
+        for filter in &filters {
+            for window in &windows {
+                let def = FilterDef {
+                    filter: filter.clone(),
+                    window: window.clone(),
+                    len: 64,
+                    shift: 32,
+                    f_sampling: 48000.0,
+                    f_lo_cut: 1234.5,
+                    f_hi_cut: 4000.0,
+                    ..Default::default()
+                };
+                let s = def.to_filter_string();
+                assert!(FilterDef::from_filter_string(&s) == Ok(def), "{}", s);
+            }
+        }
+    }
+
+    #[test]
+    fn filter_string_round_trips_options() {
+        let def = FilterDef {
+            window: Window::Hamming,
+            window_mode: WindowMode::Periodic,
+            len: 37,
+            shift: 20,
+            f_sampling: 1e6,
+            f_hi_cut: 1e5,
+            force_symmetry: true,
+            delay_offset: 4,
+            ..Default::default()
+        };
+
+        for normalization in [
+            Normalization::UnitPassbandGain,
+            Normalization::UnitEnergy,
+            Normalization::UnitSum,
+        ] {
+            let def = FilterDef {
+                normalization,
+                ..def.clone()
+            };
+            let s = def.to_filter_string();
+            assert!(FilterDef::from_filter_string(&s) == Ok(def), "{}", s);
+        }
+    }
+}
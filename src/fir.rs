@@ -1,51 +1,1377 @@
+use evalexpr::{build_operator_tree, Context, EvalexprError, EvalexprResult, Value};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::f64::consts::PI;
 use std::fmt;
+use std::sync::Arc;
 
-#[derive(Default, PartialEq, Clone)]
+/// Number of points of the DFT used to evaluate frequency responses.
+pub const DFT_LEN: usize = 256;
+/// Stopband attenuation the length suggested by [`FilterWarning::TransitionBandTooNarrow`] aims for.
+const TRANSITION_WARNING_ATTENUATION_DB: f64 = 40.0;
+/// Iterations after which the root finding of [`FilterDef::compute_zeros`] gives up.
+const ABERTH_MAX_ITERATIONS: usize = 500;
+
+/// Parameters of a windowed-sinc FIR filter design.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::{Filter, FilterDef, Window};
+///
+/// let def = FilterDef {
+///     filter: Filter::LowPass,
+///     window: Window::Hann,
+///     len: 32,
+///     shift: 16,
+///     f_sampling: 1000.0,
+///     f_lo_cut: 100.0,
+///     f_hi_cut: 200.0,
+///     ..Default::default()
+/// };
+/// assert_eq!(def.compute_filter().len(), 32);
+/// ```
+#[derive(Default, PartialEq, Clone, Serialize, Deserialize)]
 pub struct FilterDef {
     pub filter: Filter,
     pub window: Window,
+    #[serde(default)]
+    pub window_mode: WindowMode,
     pub len: usize,
     pub shift: usize,
     pub f_sampling: f64,
     pub f_lo_cut: f64,
     pub f_hi_cut: f64,
+    pub force_symmetry: bool,
+    #[serde(default)]
+    pub normalization: Normalization,
+    /// Zero taps prepended to the design, or leading taps removed from it if negative, by
+    /// [`FilterDef::equalize_group_delay`]. The length and shift include the offset.
+    #[serde(default)]
+    pub delay_offset: isize,
 }
 
 impl FilterDef {
+    /// Returns the final filter coefficients: the normalized sinc coefficients multiplied by the
+    /// window function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{Filter, FilterDef, Window};
+    ///
+    /// let def = FilterDef {
+    ///     filter: Filter::LowPass,
+    ///     window: Window::Hamming,
+    ///     len: 65,
+    ///     shift: 32,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let f = def.compute_filter();
+    ///
+    /// // Odd length and centered, so the coefficients are symmetric
+    /// assert_eq!(f.len(), 65);
+    /// assert!((f[0] - f[64]).abs() < 1e-12);
+    /// ```
     pub fn compute_filter(&self) -> Vec<f64> {
-        let filter_fn = self.filter.function();
-        let dt = 1.0 / self.f_sampling as f64;
+        if self.delay_offset != 0 {
+            return self.delay_taps(self.undelayed().compute_filter());
+        }
+
+        self.normalize(self.compute_filter_unit_passband_gain())
+    }
+
+    /// Returns the windowed filter normalized with [`Normalization::UnitPassbandGain`].
+    fn compute_filter_unit_passband_gain(&self) -> Vec<f64> {
+        self.apply_window(&self.compute_sinc_coefficients())
+    }
+
+    /// Multiplies coefficients with unit passband gain by the window, making them symmetric if
+    /// requested.
+    fn apply_window(&self, f: &[f64]) -> Vec<f64> {
+        let w = self.compute_window();
 
-        let f = (0..self.len)
+        let mut f_windowed = FilterDef::compute_filter_windowed(f, &w);
+        if self.force_symmetry {
+            FilterDef::symmetrize(&mut f_windowed);
+        }
+        f_windowed
+    }
+
+    /// Scales windowed coefficients with unit passband gain to the selected normalization.
+    fn normalize(&self, f_windowed: Vec<f64>) -> Vec<f64> {
+        match self.normalization {
+            Normalization::UnitPassbandGain => f_windowed,
+            normalization => {
+                let scale = normalization.scale(&f_windowed);
+                FilterDef::normalize_filter(&f_windowed, scale)
+            }
+        }
+    }
+
+    /// Returns the windowed filter designed by weighted least squares, with the error of the
+    /// passband weighted by `passband_weight` and the error of the stopband by `stopband_weight`.
+    ///
+    /// The unwindowed coefficients minimize the weighted squared error to the ideal response
+    /// `$\sum_k W(\omega_k) |H(\omega_k) - D(\omega_k)|^2$` with the delay given by the shift,
+    /// evaluated on a grid of 16 frequencies per tap. Equal weights give the truncated sinc, so the
+    /// result matches [`FilterDef::compute_filter`], while a heavier stopband weight moves error
+    /// energy from the stopband into the passband ripple. Both weights have to be positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let def = FilterDef {
+    ///     len: 33,
+    ///     shift: 16,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let f = def.compute_filter();
+    /// let f_weighted = def.compute_filter_weighted(1.0, 1.0);
+    /// assert!(f.iter().zip(&f_weighted).all(|(h, h_weighted)| (h - h_weighted).abs() < 1e-3));
+    /// ```
+    pub fn compute_filter_weighted(&self, passband_weight: f64, stopband_weight: f64) -> Vec<f64> {
+        if self.delay_offset != 0 {
+            let f = self
+                .undelayed()
+                .compute_filter_weighted(passband_weight, stopband_weight);
+            return self.delay_taps(f);
+        }
+        if self.len == 0 {
+            return Vec::new();
+        }
+
+        // Midpoints of the grid, the cosine sums of equal weights then vanish off the diagonal
+        let n_freqs = (16 * self.len).max(DFT_LEN);
+        let grid: Vec<(f64, f64, bool)> = (0..n_freqs)
+            .map(|k| {
+                let w = PI * (k as f64 + 0.5) / n_freqs as f64;
+                let is_passband = self.is_passband(w * self.f_sampling / (2.0 * PI));
+                let weight = if is_passband {
+                    passband_weight
+                } else {
+                    stopband_weight
+                };
+                (w, weight, is_passband)
+            })
+            .collect();
+
+        // Normal equations A h = b, A is the Toeplitz matrix of the weighted cosine sums
+        let r: Vec<f64> = (0..self.len)
+            .map(|d| {
+                grid.iter()
+                    .map(|(w, weight, _)| weight * (w * d as f64).cos())
+                    .sum()
+            })
+            .collect();
+        let a: Vec<Vec<f64>> = (0..self.len)
+            .map(|n| (0..self.len).map(|m| r[n.abs_diff(m)]).collect())
+            .collect();
+        let b: Vec<f64> = (0..self.len)
             .map(|n| {
-                filter_fn(
-                    n,
-                    self.shift,
-                    dt,
-                    self.f_lo_cut as f64,
-                    self.f_hi_cut as f64,
-                )
+                grid.iter()
+                    .filter(|(_, _, is_passband)| *is_passband)
+                    .map(|(w, weight, _)| weight * (w * (n as f64 - self.shift as f64)).cos())
+                    .sum()
             })
             .collect();
-        let g = match self.filter {
-            Filter::LowPass | Filter::BandStop => FilterDef::compute_dc_gain(&f),
-            Filter::BandPass => FilterDef::compute_gain(
-                &f,
-                self.f_lo_cut + (self.f_hi_cut - self.f_lo_cut) / 2 as f64,
+
+        let f = solve_cholesky(a, b);
+        let g = self.passband_gain(&f);
+        self.normalize(self.apply_window(&FilterDef::normalize_filter(&f, g)))
+    }
+
+    /// Scales the coefficients to unit energy `$\sum_n h[n]^2 = 1$`, as used for matched filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let f = FilterDef::normalize_to_unit_energy(vec![3.0, 4.0]);
+    /// assert_eq!(f, vec![0.6, 0.8]);
+    /// ```
+    pub fn normalize_to_unit_energy(coefficients: Vec<f64>) -> Vec<f64> {
+        let scale = Normalization::UnitEnergy.scale(&coefficients);
+
+        FilterDef::normalize_filter(&coefficients, scale)
+    }
+
+    /// Makes the coefficients exactly symmetric by replacing `$h[n]$` and `$h[N-1-n]$` with their
+    /// mean, removing any round-off asymmetry so the filter is exactly linear phase.
+    pub fn symmetrize(f: &mut [f64]) {
+        let len = f.len();
+
+        for n in 0..len / 2 {
+            let h = (f[n] + f[len - 1 - n]) / 2.0;
+            f[n] = h;
+            f[len - 1 - n] = h;
+        }
+    }
+
+    /// Returns the normalized sinc coefficients of the filter before any windowing is applied.
+    pub fn compute_sinc_coefficients(&self) -> Vec<f64> {
+        if self.delay_offset != 0 {
+            return self.delay_taps(self.undelayed().compute_sinc_coefficients());
+        }
+
+        let g = self.compute_sinc_gain();
+
+        self.raw_sinc_coefficients()
+            .into_iter()
+            .map(|h| h / g)
+            .collect()
+    }
+
+    /// Returns the impulse response formula of the filter as a LaTeX string with the parameters
+    /// substituted, e.g. for a lowpass filter
+    ///
+    /// ```text
+    /// h[n] = \frac{\sin(2\pi \cdot 300 \cdot (n-32)/1000)}{\pi \cdot (n-32)/1000} \cdot w[n]
+    /// ```
+    ///
+    /// The normalization of the coefficients to unit passband gain is not part of the formula.
+    pub fn formula_display(&self) -> String {
+        let t = format!("(n-{})/{}", self.shift, self.f_sampling);
+        let sin = |f: f64| format!("\\sin(2\\pi \\cdot {} \\cdot {})", f, t);
+        let nyquist = format!("\\sin(\\pi \\cdot (n-{}))", self.shift);
+
+        let numerator = match &self.filter {
+            Filter::LowPass => sin(self.f_hi_cut),
+            Filter::HighPass => format!("{} - {}", nyquist, sin(self.f_lo_cut)),
+            Filter::BandPass => format!("{} - {}", sin(self.f_hi_cut), sin(self.f_lo_cut)),
+            Filter::BandStop => format!(
+                "{} - {} + {}",
+                sin(self.f_lo_cut),
+                sin(self.f_hi_cut),
+                nyquist
             ),
-            Filter::HighPass => FilterDef::compute_gain(&f, self.f_sampling / 2 as f64),
+            Filter::Multiband { bands } => {
+                let terms: Vec<String> = bands
+                    .iter()
+                    .filter(|(_, _, is_passband)| *is_passband)
+                    .map(|(f_lo, f_hi, _)| format!("{} - {}", sin(*f_hi), sin(*f_lo)))
+                    .collect();
+                if terms.is_empty() {
+                    "0".to_string()
+                } else {
+                    terms.join(" + ")
+                }
+            }
+            Filter::Custom { expression } => {
+                return format!("h[n] = ({}) \\cdot w[n]", expression);
+            }
+            Filter::Kernel(_) => return "h[n] = k[n] \\cdot w[n]".to_string(),
+        };
+
+        format!(
+            "h[n] = \\frac{{{}}}{{\\pi \\cdot {}}} \\cdot w[n]",
+            numerator, t
+        )
+    }
+
+    /// Returns a single windowed, normalized filter coefficient without computing the whole filter.
+    ///
+    /// Normalizations other than [`Normalization::UnitPassbandGain`] depend on all coefficients, for
+    /// those the whole filter is computed once to find the scale.
+    pub fn coefficient_at(&self, n: usize) -> f64 {
+        self.coefficient_at_with_gain(n, self.coefficient_gain())
+    }
+
+    /// Returns an iterator lazily yielding all windowed, normalized filter coefficients.
+    pub fn coefficients(&self) -> Coefficients<'_> {
+        Coefficients {
+            def: self,
+            gain: self.coefficient_gain(),
+            n: 0,
+        }
+    }
+
+    /// Returns the gain the raw windowed coefficients are divided by to apply the normalization.
+    fn coefficient_gain(&self) -> f64 {
+        if self.delay_offset != 0 {
+            return self.undelayed().coefficient_gain();
+        }
+
+        let g = self.compute_sinc_gain();
+
+        match self.normalization {
+            Normalization::UnitPassbandGain => g,
+            normalization => g * normalization.scale(&self.compute_filter_unit_passband_gain()),
+        }
+    }
+
+    /// Returns the window sample `$w[n]$` without computing the whole window.
+    pub fn window_at(&self, n: usize) -> f64 {
+        if self.delay_offset != 0 {
+            let def = self.undelayed();
+            return self.undelayed_index(n).map_or(0.0, |n| def.window_at(n));
+        }
+
+        self.window.function(self.window_mode)(n, self.len)
+    }
+
+    /// Returns the raw sinc value `$h[n]$` before normalization and windowing.
+    pub fn filter_at(&self, n: usize) -> f64 {
+        if self.delay_offset != 0 {
+            let def = self.undelayed();
+            return self.undelayed_index(n).map_or(0.0, |n| def.filter_at(n));
+        }
+
+        let filter_fn = self.filter.function();
+        let dt = 1.0 / self.f_sampling;
+
+        filter_fn(n, self.shift, dt, self.f_lo_cut, self.f_hi_cut)
+    }
+
+    /// Returns the normalized sinc value times the window sample, the `n`-th entry of
+    /// [`FilterDef::compute_filter_windowed`] for the sinc coefficients and the window. Unlike
+    /// [`FilterDef::coefficient_at`] the coefficient is never symmetrized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let def = FilterDef {
+    ///     len: 33,
+    ///     shift: 16,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let f = FilterDef::compute_filter_windowed(
+    ///     &def.compute_sinc_coefficients(),
+    ///     &def.compute_window(),
+    /// );
+    ///
+    /// for (n, h) in f.iter().enumerate() {
+    ///     assert!((def.windowed_coefficient_at(n) - h).abs() < 1e-12);
+    /// }
+    /// ```
+    pub fn windowed_coefficient_at(&self, n: usize) -> f64 {
+        self.filter_at(n) / self.compute_sinc_gain() * self.window_at(n)
+    }
+
+    fn coefficient_at_with_gain(&self, n: usize, g: f64) -> f64 {
+        if self.delay_offset != 0 {
+            let def = self.undelayed();
+            return self
+                .undelayed_index(n)
+                .map_or(0.0, |n| def.coefficient_at_with_gain(n, g));
+        }
+
+        let window_fn = self.window.function(self.window_mode);
+        let h = |n| self.filter_at(n) * window_fn(n, self.len) / g;
+
+        if self.force_symmetry {
+            (h(n) + h(self.len - 1 - n)) / 2.0
+        } else {
+            h(n)
+        }
+    }
+
+    fn raw_sinc_coefficients(&self) -> Vec<f64> {
+        let filter_fn = self.filter.function();
+        let dt = 1.0 / self.f_sampling;
+
+        (0..self.len)
+            .map(|n| filter_fn(n, self.shift, dt, self.f_lo_cut, self.f_hi_cut))
+            .collect()
+    }
+
+    /// Returns the gain of the raw sinc coefficients used to normalize the filter, measured at DC or
+    /// the center of the passband depending on the filter type.
+    pub fn compute_sinc_gain(&self) -> f64 {
+        if self.delay_offset != 0 {
+            return self.undelayed().compute_sinc_gain();
+        }
+
+        self.passband_gain(&self.raw_sinc_coefficients())
+    }
+
+    /// Returns the gain of coefficients at DC or the center of the passband, see
+    /// [`FilterDef::compute_sinc_gain`].
+    fn passband_gain(&self, f: &[f64]) -> f64 {
+        let f = f.iter().copied();
+
+        match self.filter {
+            Filter::LowPass | Filter::BandStop => f.sum(),
+            // Even length high pass filters have a zero at the Nyquist frequency, so they are
+            // normalized in the middle of the passband as well
+            Filter::HighPass
+            | Filter::BandPass
+            | Filter::Multiband { .. }
+            | Filter::Custom { .. }
+            | Filter::Kernel(_) => {
+                gain_at(f, 2.0 * PI * self.passband_center_hz() / self.f_sampling)
+            }
+        }
+    }
+
+    /// Returns true if the filter is a Type II FIR, i.e. a symmetric filter of even length.
+    ///
+    /// Type II filters have an inherent zero at the Nyquist frequency. The sinc is centered on the
+    /// integer shift while the middle of an even length filter lies between two taps, so the taps
+    /// are only symmetric if [`FilterDef::force_symmetry`] is set and no
+    /// [`FilterDef::delay_offset`] pads or trims them.
+    pub fn is_type_ii(&self) -> bool {
+        self.len.is_multiple_of(2) && self.force_symmetry && self.delay_offset == 0
+    }
+
+    /// Lengthens an even length filter by one tap, turning a symmetric Type II filter into a Type I
+    /// filter without the Nyquist null.
+    ///
+    /// The shift is kept, so a shift of `len / 2` becomes the middle tap of the odd length and the
+    /// sinc is symmetric without [`FilterDef::force_symmetry`]. The added tap continues the windowed
+    /// sinc, unlike the zero tap of [`FilterDef::even_length_highpass_correction`] which would break
+    /// the symmetry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let mut def = FilterDef { len: 32, shift: 16, force_symmetry: true, ..Default::default() };
+    /// assert!(def.is_type_ii());
+    ///
+    /// def.ensure_odd_length();
+    /// assert_eq!((def.len, def.shift), (33, 16));
+    /// assert!(!def.is_type_ii());
+    /// ```
+    pub fn ensure_odd_length(&mut self) {
+        if self.len.is_multiple_of(2) {
+            self.len += 1;
+        }
+    }
+
+    /// Pads an even length filter with a trailing zero tap, making its length odd while leaving the
+    /// response of the existing taps unchanged.
+    pub fn even_length_highpass_correction(f: &[f64]) -> Vec<f64> {
+        let mut f = f.to_vec();
+        if f.len().is_multiple_of(2) {
+            f.push(0.0);
+        }
+        f
+    }
+
+    /// Returns the complex frequency response `$(\Re H, \Im H)$` of the windowed filter at a single
+    /// frequency in Hz.
+    pub fn frequency_response_at(&self, freq_hz: f64) -> (f64, f64) {
+        self.frequency_response_of(&self.compute_filter(), freq_hz)
+    }
+
+    /// Returns the complex frequency response of the coefficients `f` at a single frequency in Hz,
+    /// see [`FilterDef::frequency_response_at`].
+    pub fn frequency_response_of(&self, f: &[f64], freq_hz: f64) -> (f64, f64) {
+        let w = 2.0 * PI * freq_hz / self.f_sampling;
+
+        response_at(f.iter().copied(), w)
+    }
+
+    /// Returns the gain in dB of the windowed filter just below the Nyquist frequency, at
+    /// `$f_s / 2 - f_s / (100 N_{DFT})$` where the response of an even length filter does not vanish
+    /// exactly. A high pass filter should have a gain close to 0 dB here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{Filter, FilterDef};
+    ///
+    /// let def = FilterDef {
+    ///     filter: Filter::HighPass,
+    ///     len: 65,
+    ///     shift: 32,
+    ///     f_sampling: 1000.0,
+    ///     f_lo_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// assert!(def.gain_at_nyquist_db().abs() < 1.0);
+    /// ```
+    pub fn gain_at_nyquist_db(&self) -> f64 {
+        self.gain_at_nyquist_db_of(&self.compute_filter())
+    }
+
+    /// Returns the gain in dB of the coefficients `f` just below the Nyquist frequency, see
+    /// [`FilterDef::gain_at_nyquist_db`].
+    pub fn gain_at_nyquist_db_of(&self, f: &[f64]) -> f64 {
+        let epsilon = self.f_sampling / (100.0 * DFT_LEN as f64);
+        let (re, im) = self.frequency_response_of(f, self.f_sampling / 2.0 - epsilon);
+
+        10.0 * (re.powi(2) + im.powi(2)).log10()
+    }
+
+    /// Returns the group delay in samples of the windowed filter at a single frequency in Hz.
+    ///
+    /// The group delay `$-\frac{d\phi}{d\omega}$` is approximated by a central difference of the
+    /// phase at `$f \pm \Delta f$` with `$\Delta f = f_s / (100 N_{DFT})$`.
+    pub fn group_delay_at_hz(&self, freq_hz: f64) -> f64 {
+        FilterDef::compute_group_delay_at(&self.compute_filter(), freq_hz, self.f_sampling)
+    }
+
+    /// Returns the peak level in dB, relative to the gain at the passband center, of the windowed
+    /// filter between the image of the high cutoff mirrored about the Nyquist frequency
+    /// `$f_s - f_{hi}$` and the Nyquist frequency.
+    ///
+    /// A high cutoff at or above the Nyquist frequency folds the passband back onto itself, while a
+    /// high cutoff close to it leaves too little room for the transition band. Both show up as a high
+    /// level at the Nyquist frequency. Filters that pass the Nyquist frequency by design return
+    /// `$-\infty$`, for multiband filters the upper edge of the highest passband is used.
+    pub fn compute_aliasing_artifact_db(&self) -> f64 {
+        self.compute_aliasing_artifact_db_of(&self.compute_filter())
+    }
+
+    /// Returns the aliasing artifact in dB of the coefficients `f`, see
+    /// [`FilterDef::compute_aliasing_artifact_db`].
+    pub fn compute_aliasing_artifact_db_of(&self, f: &[f64]) -> f64 {
+        let f_nyquist = self.f_sampling / 2.0;
+        let f_hi_cut = match &self.filter {
+            Filter::HighPass | Filter::BandStop => return f64::NEG_INFINITY,
+            Filter::Multiband { .. } if self.is_passband(f_nyquist) => return f64::NEG_INFINITY,
+            Filter::Multiband { bands } => bands
+                .iter()
+                .filter(|(_, _, is_passband)| *is_passband)
+                .fold(0.0, |f_hi, (_, band_hi, _)| f64::max(f_hi, *band_hi)),
+            _ => self.f_hi_cut,
+        };
+
+        let magnitude = |freq_hz: f64| {
+            let (re, im) = response_at(f.iter().copied(), 2.0 * PI * freq_hz / self.f_sampling);
+            (re.powi(2) + im.powi(2)).sqrt()
+        };
+
+        let f_image = (self.f_sampling - f_hi_cut).clamp(0.0, f_nyquist);
+        let n_points = 64;
+        let peak = (0..=n_points)
+            .map(|k| magnitude(f_image + (f_nyquist - f_image) * k as f64 / n_points as f64))
+            .fold(0.0, f64::max);
+
+        20.0 * (peak / magnitude(self.passband_center_hz())).log10()
+    }
+
+    /// Returns the stopband edge, the frequency in Hz closest to the passband at which the windowed
+    /// filter first reaches `target_attenuation_db` of attenuation, or `None` if it never does.
+    ///
+    /// The response is searched from the center of the (first) passband towards the Nyquist
+    /// frequency, or towards DC for highpass filters. The first crossing found on a grid of
+    /// [`DFT_LEN`] points is refined by bisection.
+    pub fn stopband_start_hz(&self, target_attenuation_db: f64) -> Option<f64> {
+        self.stopband_start_hz_of(&self.compute_filter(), target_attenuation_db)
+    }
+
+    /// Returns the stopband edge in Hz of the coefficients `f`, see
+    /// [`FilterDef::stopband_start_hz`].
+    pub fn stopband_start_hz_of(&self, f: &[f64], target_attenuation_db: f64) -> Option<f64> {
+        self.band_edge_hz(f, |db| db <= -target_attenuation_db)
+            .map(|(_, f_outside)| f_outside)
+    }
+
+    /// Returns the passband edge, the frequency in Hz up to which the magnitude of the windowed
+    /// filter stays within `$\pm$max_ripple_db` of unity gain, or `None` if it is already outside the
+    /// tolerance at the passband center.
+    ///
+    /// The response is searched in the same direction as for [`FilterDef::stopband_start_hz`]. If
+    /// the tolerance is never exceeded the Nyquist frequency, or DC for highpass filters, is returned.
+    pub fn passband_end_hz(&self, max_ripple_db: f64) -> Option<f64> {
+        self.passband_end_hz_of(&self.compute_filter(), max_ripple_db)
+    }
+
+    /// Returns the passband edge in Hz of the coefficients `f`, see [`FilterDef::passband_end_hz`].
+    pub fn passband_end_hz_of(&self, f: &[f64], max_ripple_db: f64) -> Option<f64> {
+        let f_center = self.passband_center_hz();
+        if self.magnitude_db_at(f, f_center).abs() > max_ripple_db {
+            return None;
+        }
+
+        match self.band_edge_hz(f, |db| db.abs() > max_ripple_db) {
+            Some((f_inside, _)) => Some(f_inside),
+            None if self.filter == Filter::HighPass => Some(0.0),
+            None => Some(self.f_sampling / 2.0),
+        }
+    }
+
+    /// Returns the cutoff in Hz of the design that the search of [`FilterDef::actual_3db_hz`]
+    /// reaches first, the upper passband edge or the lower one for highpass and bandstop filters.
+    ///
+    /// This is where the ideal brick wall response ends. The truncated sinc crosses half its
+    /// passband amplitude there, so even without a window the actual 3 dB point lies slightly inside
+    /// the passband, and a window moves it further.
+    pub fn theoretical_3db_hz(&self) -> f64 {
+        match &self.filter {
+            Filter::LowPass | Filter::BandPass | Filter::Custom { .. } | Filter::Kernel(_) => {
+                self.f_hi_cut
+            }
+            Filter::HighPass | Filter::BandStop => self.f_lo_cut,
+            Filter::Multiband { bands } => bands
+                .iter()
+                .find(|(_, _, is_passband)| *is_passband)
+                .map_or(0.0, |(_, f_hi, _)| *f_hi),
+        }
+    }
+
+    /// Returns the frequency in Hz at which the windowed filter first falls 3 dB below unity gain,
+    /// searched in the same direction as for [`FilterDef::stopband_start_hz`], or NaN if it never
+    /// does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{FilterDef, Window};
+    ///
+    /// let def = FilterDef {
+    ///     window: Window::Hamming,
+    ///     len: 65,
+    ///     shift: 32,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // The window moves the 3 dB point into the passband
+    /// let shift = def.actual_3db_hz() - def.theoretical_3db_hz();
+    /// assert!(shift < 0.0 && shift > -20.0);
+    /// ```
+    pub fn actual_3db_hz(&self) -> f64 {
+        self.actual_3db_hz_of(&self.compute_filter())
+    }
+
+    /// Returns the 3 dB frequency in Hz of the coefficients `f`, see [`FilterDef::actual_3db_hz`].
+    pub fn actual_3db_hz_of(&self, f: &[f64]) -> f64 {
+        self.band_edge_hz(f, |db| db <= -3.0)
+            .map_or(f64::NAN, |(_, f_outside)| f_outside)
+    }
+
+    /// Searches from the passband center outwards for the first frequency where `is_outside` holds
+    /// for the magnitude in dB, returning the bisected frequencies just before and after the edge.
+    fn band_edge_hz(&self, f: &[f64], is_outside: impl Fn(f64) -> bool) -> Option<(f64, f64)> {
+        let f_start = self.passband_center_hz();
+        let f_end = if self.filter == Filter::HighPass {
+            0.0
+        } else {
+            self.f_sampling / 2.0
         };
-        FilterDef::normalize_filter(&f, g)
+        let outside = |freq_hz: f64| is_outside(self.magnitude_db_at(f, freq_hz));
+
+        let step = (f_end - f_start) / DFT_LEN as f64;
+        let k = (0..=DFT_LEN).find(|k| outside(f_start + *k as f64 * step))?;
+        if k == 0 {
+            return Some((f_start, f_start));
+        }
+
+        let (mut f_inside, mut f_outside) =
+            (f_start + (k - 1) as f64 * step, f_start + k as f64 * step);
+        for _ in 0..32 {
+            let f_mid = (f_inside + f_outside) / 2.0;
+            if outside(f_mid) {
+                f_outside = f_mid;
+            } else {
+                f_inside = f_mid;
+            }
+        }
+
+        Some((f_inside, f_outside))
+    }
+
+    fn magnitude_db_at(&self, f: &[f64], freq_hz: f64) -> f64 {
+        let w = 2.0 * PI * freq_hz / self.f_sampling;
+
+        20.0 * gain_at(f.iter().copied(), w).log10()
+    }
+
+    /// Returns the group delay in samples the filter is designed for.
+    ///
+    /// A filter symmetric around tap `shift`, i.e. of odd length with `shift = (len - 1) / 2`, is
+    /// linear phase and its group delay is exactly `shift` samples at all frequencies. For other
+    /// shifts the truncated sinc is not symmetric around `shift` and this is only an approximation of
+    /// the actual delay, see [`FilterDef::actual_group_delay_passband_samples`].
+    pub fn expected_group_delay_samples(&self) -> f64 {
+        self.shift as f64
+    }
+
+    /// Returns the mean group delay in samples of the windowed filter over its passband, evaluated
+    /// on the positive frequency bins of the DFT grid.
+    pub fn actual_group_delay_passband_samples(&self) -> f64 {
+        self.actual_group_delay_passband_samples_of(&self.compute_filter())
+    }
+
+    /// Returns the mean passband group delay in samples of the coefficients `f`, see
+    /// [`FilterDef::actual_group_delay_passband_samples`].
+    pub fn actual_group_delay_passband_samples_of(&self, f: &[f64]) -> f64 {
+        let df = self.f_sampling / DFT_LEN as f64;
+
+        let (sum, count) = (0..DFT_LEN / 2)
+            .map(|k| k as f64 * df)
+            .filter(|freq| self.is_passband(*freq))
+            .map(|freq| FilterDef::compute_group_delay_at(f, freq, self.f_sampling))
+            .filter(|tau| tau.is_finite())
+            .fold((0.0, 0), |(sum, count), tau| (sum + tau, count + 1));
+
+        if count > 0 {
+            sum / count as f64
+        } else {
+            FilterDef::compute_group_delay_at(f, self.passband_center_hz(), self.f_sampling)
+        }
+    }
+
+    /// Returns the peak to peak variation of the group delay over the passband relative to its mean
+    /// `$20 \log_{10} \frac{\tau_{max} - \tau_{min}}{\bar\tau}$` in dB, evaluated on the passband
+    /// frequencies of [`FilterDef::actual_group_delay_passband_samples`].
+    ///
+    /// A linear phase filter has no variation, which shows up as an infinitely negative level or a
+    /// level given by the numerical precision of the group delay.
+    pub fn group_delay_variation_db(&self) -> f64 {
+        self.group_delay_variation_db_of(&self.compute_filter())
+    }
+
+    /// Returns the passband group delay variation in dB of the coefficients `f`, see
+    /// [`FilterDef::group_delay_variation_db`].
+    pub fn group_delay_variation_db_of(&self, f: &[f64]) -> f64 {
+        let df = self.f_sampling / DFT_LEN as f64;
+
+        let (min, max) = (0..DFT_LEN / 2)
+            .map(|k| k as f64 * df)
+            .filter(|freq| self.is_passband(*freq))
+            .map(|freq| FilterDef::compute_group_delay_at(f, freq, self.f_sampling))
+            .filter(|tau| tau.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), tau| {
+                (min.min(tau), max.max(tau))
+            });
+
+        20.0 * ((max - min) / self.actual_group_delay_passband_samples_of(f)).log10()
+    }
+
+    /// Returns the minimum phase filter with the same magnitude response as the windowed filter.
+    ///
+    /// The conversion uses the real cepstrum `$c[n]$` of the magnitude response. Folding the
+    /// anticausal part of the cepstrum onto the causal part and exponentiating its spectrum gives
+    /// the minimum phase spectrum:
+    ///
+    /// ```math
+    /// H_{min}[k] = \exp\left(\text{DFT}\{c_{min}\}[k]\right), \quad
+    /// c_{min}[n] = \begin{cases} c[n] & n = 0, \frac{N}{2} \\ 2 c[n] & 0 < n < \frac{N}{2} \\ 0 & n > \frac{N}{2} \end{cases}
+    /// ```
+    ///
+    /// [\[1\]](https://ccrma.stanford.edu/~jos/sasp/Minimum_Phase_Filter_Design.html) Julius O. Smith III.
+    /// Spectral Audio Signal Processing
+    pub fn minimum_phase_equivalent(&self) -> Vec<f64> {
+        let f = self.compute_filter();
+        let n_fft = (8 * f.len()).next_power_of_two().max(1024);
+
+        let mut x = vec![(0.0, 0.0); n_fft];
+        for (x, h) in x.iter_mut().zip(&f) {
+            *x = (*h, 0.0);
+        }
+
+        // Real cepstrum of the magnitude response, the floor keeps the log finite at spectral nulls
+        let log_mag: Vec<(f64, f64)> = dft(&x, false)
+            .iter()
+            .map(|(re, im)| ((re.powi(2) + im.powi(2)).sqrt().max(1e-12).ln(), 0.0))
+            .collect();
+        let cepstrum = dft(&log_mag, true);
+
+        let folded: Vec<(f64, f64)> = cepstrum
+            .iter()
+            .enumerate()
+            .map(|(n, (c, _))| match n {
+                0 => (*c, 0.0),
+                n if n < n_fft / 2 => (2.0 * c, 0.0),
+                n if n == n_fft / 2 => (*c, 0.0),
+                _ => (0.0, 0.0),
+            })
+            .collect();
+
+        let spectrum: Vec<(f64, f64)> = dft(&folded, false)
+            .iter()
+            .map(|(re, im)| (re.exp() * im.cos(), re.exp() * im.sin()))
+            .collect();
+
+        dft(&spectrum, true)
+            .iter()
+            .take(f.len())
+            .map(|(re, _)| *re)
+            .collect()
+    }
+
+    /// Returns the frequency in Hz at the center of the (first) passband of the filter.
+    pub fn passband_center_hz(&self) -> f64 {
+        match &self.filter {
+            Filter::LowPass => self.f_hi_cut / 2.0,
+            Filter::HighPass => (self.f_lo_cut + self.f_sampling / 2.0) / 2.0,
+            Filter::BandPass => (self.f_lo_cut + self.f_hi_cut) / 2.0,
+            Filter::BandStop => self.f_lo_cut / 2.0,
+            Filter::Multiband { bands } => bands
+                .iter()
+                .find(|(_, _, is_passband)| *is_passband)
+                .map_or(0.0, |(f_lo, f_hi, _)| (f_lo + f_hi) / 2.0),
+            Filter::Custom { .. } | Filter::Kernel(_) => (self.f_lo_cut + self.f_hi_cut) / 2.0,
+        }
+    }
+
+    /// Returns true if the sinc is centered on one of the taps, so no significant coefficient would
+    /// be needed before the first input sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let def = FilterDef { len: 32, shift: 16, ..Default::default() };
+    /// assert!(def.is_causal());
+    /// assert!(!FilterDef { shift: 32, ..def }.is_causal());
+    /// ```
+    pub fn is_causal(&self) -> bool {
+        self.shift < self.len
+    }
+
+    /// Returns the output latency of the filter in samples, the shift of the sinc peak from the
+    /// first tap.
+    pub fn causality_delay_samples(&self) -> usize {
+        self.shift
+    }
+
+    /// Moves the peak of the filter to `target_delay_samples` to match the group delay of other
+    /// filters in a processing chain.
+    ///
+    /// If the shift is below the target, zero taps are prepended to the windowed filter, increasing
+    /// the latency. If the shift is above the target, leading taps are removed, at most all of them.
+    /// The remaining taps are bit for bit those of the design before, see
+    /// [`FilterDef::delay_offset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let mut def = FilterDef {
+    ///     len: 33,
+    ///     shift: 16,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let f = def.compute_filter();
+    ///
+    /// def.equalize_group_delay(20);
+    /// let f_delayed = def.compute_filter();
+    /// assert_eq!((def.len, def.shift), (37, 20));
+    /// assert_eq!(f_delayed[..4], [0.0; 4]);
+    /// assert_eq!(f_delayed[4..], f[..]);
+    /// ```
+    pub fn equalize_group_delay(&mut self, target_delay_samples: usize) {
+        let delay = (target_delay_samples as isize - self.shift as isize).max(-(self.len as isize));
+
+        self.len = self.len.saturating_add_signed(delay);
+        self.shift = self.shift.saturating_add_signed(delay);
+        self.delay_offset += delay;
+    }
+
+    /// Returns the design without the [`FilterDef::delay_offset`], whose taps are padded or trimmed.
+    fn undelayed(&self) -> FilterDef {
+        FilterDef {
+            len: self.len.saturating_add_signed(-self.delay_offset),
+            shift: self.shift.saturating_add_signed(-self.delay_offset),
+            delay_offset: 0,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the tap of the [undelayed](FilterDef::undelayed) design at tap `n`, `None` for
+    /// prepended zeros.
+    fn undelayed_index(&self, n: usize) -> Option<usize> {
+        n.checked_add_signed(-self.delay_offset)
+    }
+
+    /// Applies the [`FilterDef::delay_offset`] to taps of the [undelayed](FilterDef::undelayed)
+    /// design.
+    fn delay_taps(&self, f: Vec<f64>) -> Vec<f64> {
+        let zeros = self.delay_offset.max(0) as usize;
+        let trimmed = self.delay_offset.min(0).unsigned_abs();
+
+        std::iter::repeat_n(0.0, zeros)
+            .chain(f.into_iter().skip(trimmed))
+            .take(self.len)
+            .collect()
+    }
+
+    /// Returns the `(signal_len + len - 1) × signal_len` Toeplitz matrix `H` writing the
+    /// convolution of a signal `x` with the windowed filter as the product `y = H x`, as rows of `H`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let def = FilterDef {
+    ///     len: 9,
+    ///     shift: 4,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let h = def.convolution_matrix(4);
+    /// assert_eq!(h.len(), 12);
+    ///
+    /// // The product with a unit impulse is the coefficient vector
+    /// let impulse = [1.0, 0.0, 0.0, 0.0];
+    /// let y: Vec<f64> = h
+    ///     .iter()
+    ///     .map(|row| row.iter().zip(&impulse).map(|(h, x)| h * x).sum())
+    ///     .collect();
+    /// assert_eq!(y[..9], def.compute_filter()[..]);
+    /// assert!(y[9..].iter().all(|y| *y == 0.0));
+    /// ```
+    pub fn convolution_matrix(&self, signal_len: usize) -> Vec<Vec<f64>> {
+        let f = self.compute_filter();
+        if f.is_empty() || signal_len == 0 {
+            return Vec::new();
+        }
+
+        (0..signal_len + f.len() - 1)
+            .map(|row| {
+                (0..signal_len)
+                    .map(|column| {
+                        row.checked_sub(column)
+                            .and_then(|k| f.get(k))
+                            .copied()
+                            .unwrap_or(0.0)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Filters a signal with the coefficients using the overlap-add method.
+    ///
+    /// The signal is split into blocks of `block_len` samples which are convolved with the filter
+    /// separately and summed back together. The output is truncated to the length of the signal.
+    pub fn apply_overlap_add(f: &[f64], signal: &[f64], block_len: usize) -> Vec<f64> {
+        let mut y = vec![0.0; signal.len() + f.len().saturating_sub(1)];
+
+        for (b, block) in signal.chunks(block_len.max(1)).enumerate() {
+            let offset = b * block_len.max(1);
+            for (n, y_n) in convolve(block, f).into_iter().enumerate() {
+                y[offset + n] += y_n;
+            }
+        }
+
+        y.truncate(signal.len());
+        y
+    }
+
+    /// Returns the coefficients in reverse order `$h[N-1-n]$`.
+    ///
+    /// The reversed filter has the same magnitude response but the negated phase response of the
+    /// original, so its group delay is `$N-1$` minus the original one.
+    pub fn flip(coefficients: &[f64]) -> Vec<f64> {
+        coefficients.iter().rev().copied().collect()
+    }
+
+    /// Returns the zeros of the transfer function `$H(z) = \sum_n h[n] z^{-n}$` as `(re, im)` pairs.
+    ///
+    /// The zeros are the roots of the polynomial `$h[0] z^{N-1} + h[1] z^{N-2} + \dots + h[N-1]$`,
+    /// found simultaneously with the Aberth-Ehrlich iteration. The only pole of a FIR filter is the
+    /// one of order `$N-1$` at the origin. Trailing zero coefficients give zeros at the origin,
+    /// leading zero coefficients lower the order of the polynomial.
+    ///
+    /// Zeros of a real filter come in complex conjugate pairs, those of a linear phase filter
+    /// additionally in pairs `$z_k, 1/z_k$` mirrored about the unit circle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// // z^2 - 1 has its zeros at 1 and -1
+    /// let mut zeros = FilterDef::compute_zeros(&[1.0, 0.0, -1.0]);
+    /// zeros.sort_by(|a, b| a.0.total_cmp(&b.0));
+    /// assert!((zeros[0].0 + 1.0).abs() < 1e-9 && (zeros[1].0 - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn compute_zeros(coefficients: &[f64]) -> Vec<(f64, f64)> {
+        let first = coefficients.iter().position(|h| *h != 0.0);
+        let last = coefficients.iter().rposition(|h| *h != 0.0);
+        let (Some(first), Some(last)) = (first, last) else {
+            return Vec::new();
+        };
+        let mut zeros = vec![(0.0, 0.0); coefficients.len() - 1 - last];
+
+        let p = &coefficients[first..=last];
+        let degree = p.len() - 1;
+        if degree == 0 {
+            return zeros;
+        }
+
+        // Start on a circle of the geometric mean radius of the roots, rotated off the real axis
+        let radius = (p[degree] / p[0]).abs().powf(1.0 / degree as f64);
+        let mut roots: Vec<(f64, f64)> = (0..degree)
+            .map(|k| {
+                let theta = 2.0 * PI * k as f64 / degree as f64 + 0.4;
+                (radius * theta.cos(), radius * theta.sin())
+            })
+            .collect();
+
+        for _ in 0..ABERTH_MAX_ITERATIONS {
+            let mut max_step: f64 = 0.0;
+            for k in 0..degree {
+                let z = roots[k];
+                let (value, derivative) = p.iter().fold(((0.0, 0.0), (0.0, 0.0)), |(v, d), h| {
+                    (c_add(c_mul(v, z), (*h, 0.0)), c_add(c_mul(d, z), v))
+                });
+                if value == (0.0, 0.0) {
+                    continue;
+                }
+
+                let newton = c_div(value, derivative);
+                let repulsion = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != k)
+                    .fold((0.0, 0.0), |sum, (_, z_j)| {
+                        c_add(sum, c_div((1.0, 0.0), c_sub(z, *z_j)))
+                    });
+                let step = c_div(newton, c_sub((1.0, 0.0), c_mul(newton, repulsion)));
+
+                roots[k] = c_sub(z, step);
+                max_step = max_step.max(c_abs(step) / c_abs(roots[k]).max(1.0));
+            }
+            if max_step < 1e-14 {
+                break;
+            }
+        }
+
+        zeros.extend(roots);
+        zeros
+    }
+
+    /// Filters a signal forward and backward with the coefficients, cancelling out the phase
+    /// response.
+    ///
+    /// The signal is filtered, reversed, filtered again and reversed back. The result is a signal
+    /// filtered with the squared magnitude response of the filter and no delay. Both passes keep the
+    /// full convolution so that the output is aligned to the input, which is treated as zero outside
+    /// of its samples.
+    pub fn zero_phase_filter(coefficients: &[f64], signal: &[f64]) -> Vec<f64> {
+        let tail = coefficients.len().saturating_sub(1);
+
+        let forward = convolve(coefficients, signal);
+        let backward = convolve(coefficients, &FilterDef::flip(&forward));
+
+        FilterDef::flip(&backward)
+            .into_iter()
+            .skip(tail)
+            .take(signal.len())
+            .collect()
+    }
+
+    /// Returns a pair of `(input, expected_output)` test vectors for verifying an implementation of
+    /// the filter, where the input is a linear chirp sweeping from 0 Hz to the Nyquist frequency.
+    pub fn generate_test_vectors(&self, n_samples: usize) -> (Vec<f64>, Vec<f64>) {
+        self.apply_to_chirp(n_samples)
+    }
+
+    /// Returns `(chirp_input, filtered_output)` for a [`test_chirp`] of `n_samples`. As the chirp
+    /// frequency rises linearly with time, the output envelope traces the magnitude response, with
+    /// the stopbands showing up as suppressed stretches of the signal.
+    pub fn apply_to_chirp(&self, n_samples: usize) -> (Vec<f64>, Vec<f64>) {
+        let input = test_chirp(n_samples, self.f_sampling);
+        let output = FilterDef::apply_overlap_add(&self.compute_filter(), &input, DFT_LEN);
+
+        (input, output)
+    }
+
+    /// Returns the coefficients of [`FilterDef::compute_filter`], or an error if the parameters leave
+    /// no gain to normalize the coefficients by, which would turn them into NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{Filter, FilterDef, FilterError};
+    ///
+    /// let def = FilterDef {
+    ///     filter: Filter::BandPass,
+    ///     len: 65,
+    ///     shift: 32,
+    ///     f_sampling: 1000.0,
+    ///     f_lo_cut: 200.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Normalizing the all zero coefficients of a band pass without bandwidth gives NaN
+    /// assert!(def.compute_sinc_coefficients().iter().all(|h| h.is_nan()));
+    /// assert_eq!(def.try_compute_filter(), Err(FilterError::ZeroBandwidth));
+    /// ```
+    pub fn try_compute_filter(&self) -> Result<Vec<f64>, FilterError> {
+        self.validate()?;
+        Ok(self.compute_filter())
+    }
+
+    /// Returns an error for the parameter combinations that cannot produce a filter.
+    pub fn validate(&self) -> Result<(), FilterError> {
+        match self.filter {
+            Filter::BandPass if self.f_lo_cut == self.f_hi_cut => Err(FilterError::ZeroBandwidth),
+            Filter::BandStop if self.f_lo_cut == 0.0 => Err(FilterError::ZeroBandwidth),
+            Filter::LowPass if self.f_hi_cut == 0.0 => Err(FilterError::ZeroBandwidth),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the parameter combinations that still produce a filter but may make the design behave
+    /// unexpectedly.
+    pub fn warnings(&self) -> Vec<FilterWarning> {
+        let mut warnings = Vec::new();
+
+        match &self.filter {
+            Filter::Multiband { bands } => {
+                if bands
+                    .iter()
+                    .any(|(_, f_hi, _)| *f_hi > self.f_sampling / 2.0)
+                {
+                    warnings.push(FilterWarning::HighCutAboveNyquist);
+                }
+                warnings.extend(validate_bands(bands));
+            }
+            _ if self.f_hi_cut > self.f_sampling / 2.0 => {
+                warnings.push(FilterWarning::HighCutAboveNyquist);
+            }
+            _ => (),
+        }
+        if self.shift != self.len / 2 {
+            warnings.push(FilterWarning::NonLinearPhase);
+        }
+        if self.len < 5 {
+            warnings.push(FilterWarning::ShortLength);
+        }
+        if self.filter == Filter::HighPass && self.is_type_ii() {
+            warnings.push(FilterWarning::EvenLengthHighPass);
+        }
+        if let Some(width) = self.narrowest_band_hz() {
+            let transition_width =
+                self.f_sampling * self.window.characteristic_constant() / self.len.max(1) as f64;
+            if width > 0.0 && width < transition_width {
+                warnings.push(FilterWarning::TransitionBandTooNarrow {
+                    required_len: FilterDef::len_for_attenuation(
+                        TRANSITION_WARNING_ATTENUATION_DB,
+                        &self.window,
+                        width,
+                        self.f_sampling,
+                    ),
+                });
+            }
+        }
+
+        warnings
+    }
+
+    /// Returns the width in Hz of the narrowest pass or stop band the transitions have to fit into.
+    ///
+    /// Low and high pass filters are limited by the distance of their cutoff to DC and the Nyquist
+    /// frequency, the other filter types by the distance between their cutoffs. The bands of custom
+    /// kernels are unknown.
+    fn narrowest_band_hz(&self) -> Option<f64> {
+        let nyquist = self.f_sampling / 2.0;
+
+        match &self.filter {
+            Filter::LowPass => Some(self.f_hi_cut.min(nyquist - self.f_hi_cut)),
+            Filter::HighPass => Some(self.f_lo_cut.min(nyquist - self.f_lo_cut)),
+            Filter::BandPass | Filter::BandStop => Some(self.f_hi_cut - self.f_lo_cut),
+            Filter::Multiband { bands } => bands
+                .iter()
+                .map(|(f_lo, f_hi, _)| f_hi - f_lo)
+                .reduce(f64::min),
+            Filter::Custom { .. } | Filter::Kernel(_) => None,
+        }
+    }
+
+    /// Estimates the filter length required to reach `attenuation_db` of stopband attenuation with a
+    /// transition band of `transition_width_hz`.
+    ///
+    /// The estimate is the larger of the window specific length
+    /// `window.characteristic_constant() * f_sampling / transition_width_hz` and Kaiser's length
+    /// estimate for the attenuation, since a window alone cannot reach attenuations below its
+    /// sidelobe level.
+    pub fn len_for_attenuation(
+        attenuation_db: f64,
+        window: &Window,
+        transition_width_hz: f64,
+        f_sampling: f64,
+    ) -> usize {
+        let df = transition_width_hz / f_sampling;
+        let window_len = window.characteristic_constant() / df;
+        let kaiser_len = (attenuation_db - 7.95) / (14.36 * df) + 1.0;
+
+        window_len.max(kaiser_len).ceil().max(1.0) as usize
+    }
+
+    /// Designs a filter reaching `attenuation_db` of stopband attenuation with the shortest length
+    /// the window allows.
+    ///
+    /// The length is estimated by [`FilterDef::len_for_attenuation`] with the transition band taking
+    /// up the whole of the narrowest pass or stop band, the same limit
+    /// [`FilterWarning::TransitionBandTooNarrow`] checks. Custom kernels, whose bands are unknown,
+    /// assume a transition band of a quarter of the sampling frequency. The length is rounded up to
+    /// an odd number and `shift = (len - 1) / 2`, so the filter is linear phase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{Filter, FilterDef, Window};
+    ///
+    /// let def = FilterDef::design_from_spec(Filter::LowPass, Window::Hamming, 0.0, 100.0, 1000.0, 50.0);
+    /// assert_eq!(def.len % 2, 1);
+    /// assert_eq!(def.shift, (def.len - 1) / 2);
+    /// assert!(def.warnings().is_empty());
+    ///
+    /// assert!(FilterDef::lowpass(100.0, 1000.0, 50.0, Window::Hamming) == def);
+    /// ```
+    pub fn design_from_spec(
+        filter_type: Filter,
+        window: Window,
+        f_lo_cut: f64,
+        f_hi_cut: f64,
+        f_sampling: f64,
+        attenuation_db: f64,
+    ) -> FilterDef {
+        let mut def = FilterDef {
+            filter: filter_type,
+            window,
+            f_sampling,
+            f_lo_cut,
+            f_hi_cut,
+            ..Default::default()
+        };
+
+        let transition_width_hz = def.narrowest_band_hz().unwrap_or(f_sampling / 4.0);
+        let len = FilterDef::len_for_attenuation(
+            attenuation_db,
+            &def.window,
+            transition_width_hz,
+            f_sampling,
+        );
+        def.len = len | 1;
+        def.shift = (def.len - 1) / 2;
+        def
+    }
+
+    /// Designs a lowpass filter, see [`FilterDef::design_from_spec`].
+    pub fn lowpass(
+        f_hi_cut: f64,
+        f_sampling: f64,
+        attenuation_db: f64,
+        window: Window,
+    ) -> FilterDef {
+        FilterDef::design_from_spec(
+            Filter::LowPass,
+            window,
+            0.0,
+            f_hi_cut,
+            f_sampling,
+            attenuation_db,
+        )
     }
 
+    /// Designs a highpass filter, see [`FilterDef::design_from_spec`].
+    pub fn highpass(
+        f_lo_cut: f64,
+        f_sampling: f64,
+        attenuation_db: f64,
+        window: Window,
+    ) -> FilterDef {
+        FilterDef::design_from_spec(
+            Filter::HighPass,
+            window,
+            f_lo_cut,
+            0.0,
+            f_sampling,
+            attenuation_db,
+        )
+    }
+
+    /// Designs a bandpass filter, see [`FilterDef::design_from_spec`].
+    pub fn bandpass(
+        f_lo_cut: f64,
+        f_hi_cut: f64,
+        f_sampling: f64,
+        attenuation_db: f64,
+        window: Window,
+    ) -> FilterDef {
+        FilterDef::design_from_spec(
+            Filter::BandPass,
+            window,
+            f_lo_cut,
+            f_hi_cut,
+            f_sampling,
+            attenuation_db,
+        )
+    }
+
+    /// Designs a bandstop filter, see [`FilterDef::design_from_spec`].
+    pub fn bandstop(
+        f_lo_cut: f64,
+        f_hi_cut: f64,
+        f_sampling: f64,
+        attenuation_db: f64,
+        window: Window,
+    ) -> FilterDef {
+        FilterDef::design_from_spec(
+            Filter::BandStop,
+            window,
+            f_lo_cut,
+            f_hi_cut,
+            f_sampling,
+            attenuation_db,
+        )
+    }
+
+    /// Returns the `len` samples of the window of the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::{FilterDef, Window};
+    ///
+    /// let def = FilterDef {
+    ///     window: Window::Hann,
+    ///     len: 9,
+    ///     ..Default::default()
+    /// };
+    /// let w = def.compute_window();
+    ///
+    /// // The symmetric Hann window is zero at both ends and one in the center
+    /// assert_eq!(w[0], 0.0);
+    /// assert!(w[8].abs() < 1e-12);
+    /// assert!((w[4] - 1.0).abs() < 1e-12);
+    /// ```
     pub fn compute_window(&self) -> Vec<f64> {
-        let window_fn = self.window.function();
+        if self.delay_offset != 0 {
+            return self.delay_taps(self.undelayed().compute_window());
+        }
+
+        let window_fn = self.window.function(self.window_mode);
 
-        (0..self.len).map(|n| window_fn(n, self.len - 1)).collect()
+        (0..self.len).map(|n| window_fn(n, self.len)).collect()
     }
 
-    pub fn compute_filter_windowed(f: &Vec<f64>, w: &Vec<f64>) -> Vec<f64> {
+    /// Multiplies the filter coefficients by the window sample by sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the filter and window lengths differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let f = FilterDef::compute_filter_windowed(&[1.0, 2.0, 3.0], &[0.5, 1.0, 0.5]);
+    /// assert_eq!(f, vec![0.5, 2.0, 1.5]);
+    /// ```
+    pub fn compute_filter_windowed(f: &[f64], w: &[f64]) -> Vec<f64> {
         if f.len() != w.len() {
             panic!("fn_compute_filter_windowed: cannot multiply vec of different lengths")
         }
@@ -53,33 +1379,1043 @@ impl FilterDef {
         f.iter().zip(w).map(|(f, w)| f * w).collect()
     }
 
-    pub fn compute_dc_gain(f: &Vec<f64>) -> f64 {
-        f.into_iter().fold(0.0, |mut g, h| {
+    /// Returns the gain at 0 Hz, the sum of the coefficients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// assert_eq!(FilterDef::compute_dc_gain(&[0.25, 0.5, 0.25]), 1.0);
+    /// ```
+    pub fn compute_dc_gain(f: &[f64]) -> f64 {
+        f.iter().fold(0.0, |mut g, h| {
             g += h;
             g
         })
     }
 
-    pub fn compute_gain(f: &Vec<f64>, w: f64) -> f64 {
-        let mut n = 0;
-        let (re, im) = f.into_iter().fold((0.0, 0.0), |(mut re, mut im), h| {
-            re += h * (w * n as f64).cos();
-            im -= h * (w * n as f64).sin();
-            n += 1;
+    /// Returns the magnitude of the frequency response at the angular frequency `w` in radians per
+    /// sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    /// use std::f64::consts::PI;
+    ///
+    /// // A two tap moving average passes DC and blocks the Nyquist frequency
+    /// let f = [0.5, 0.5];
+    /// assert!((FilterDef::compute_gain(&f, 0.0) - 1.0).abs() < 1e-12);
+    /// assert!(FilterDef::compute_gain(&f, PI) < 1e-12);
+    /// ```
+    pub fn compute_gain(f: &[f64], w: f64) -> f64 {
+        gain_at(f.iter().copied(), w)
+    }
+
+    /// Returns the magnitude response in dB of the coefficients at arbitrary frequencies in Hz.
+    ///
+    /// Unlike the DFT the frequencies need not be uniformly spaced, so the band edges can be
+    /// sampled densely while the flat bands get few points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let db = FilterDef::compute_magnitude_at_frequencies(&[0.5, 0.5], &[0.0, 250.0], 1000.0);
+    /// assert!(db[0].abs() < 1e-12);
+    /// assert!((db[1] + 3.0103).abs() < 1e-4);
+    /// ```
+    pub fn compute_magnitude_at_frequencies(
+        coefficients: &[f64],
+        frequencies_hz: &[f64],
+        f_sampling: f64,
+    ) -> Vec<f64> {
+        frequencies_hz
+            .iter()
+            .map(|freq| {
+                let gain = gain_at(coefficients.iter().copied(), 2.0 * PI * freq / f_sampling);
+                20.0 * gain.log10()
+            })
+            .collect()
+    }
+
+    /// Returns the group delay in samples of the coefficients at a single frequency in Hz, see
+    /// [`FilterDef::group_delay_at_hz`].
+    pub fn compute_group_delay_at(f: &[f64], freq_hz: f64, f_sampling: f64) -> f64 {
+        let delta_f = f_sampling / (DFT_LEN * 100) as f64;
+        let phase = |freq: f64| {
+            let (re, im) = response_at(f.iter().copied(), 2.0 * PI * freq / f_sampling);
+            im.atan2(re)
+        };
+
+        let mut delta_phase = phase(freq_hz + delta_f) - phase(freq_hz - delta_f);
+        if delta_phase > PI {
+            delta_phase -= 2.0 * PI;
+        } else if delta_phase < -PI {
+            delta_phase += 2.0 * PI;
+        }
+
+        -delta_phase / (2.0 * 2.0 * PI * delta_f / f_sampling)
+    }
+
+    /// Returns the group delay in samples of the coefficients as `[Hz, samples]` pairs at `n_points`
+    /// frequencies from 0 Hz up to the Nyquist frequency.
+    ///
+    /// The group delay is evaluated exactly, without finite differences, as
+    ///
+    /// ```math
+    /// \tau(\omega) = -\frac{d \arg H(\omega)}{d\omega} = \Re\left\{\frac{H_n(\omega)}{H(\omega)}\right\}
+    /// ```
+    ///
+    /// where `$H_n$` is the DTFT of `$n h[n]$`.
+    pub fn compute_group_delay_response(
+        f: &[f64],
+        f_sampling: f64,
+        n_points: usize,
+    ) -> Vec<[f64; 2]> {
+        let df = f_sampling / 2.0 / n_points as f64;
+
+        (0..n_points)
+            .map(|k| {
+                let freq = k as f64 * df;
+                let w = 2.0 * PI * freq / f_sampling;
+                let (re, im) = response_at(f.iter().copied(), w);
+                let (re_n, im_n) = response_at(f.iter().enumerate().map(|(n, h)| n as f64 * h), w);
+
+                // Re{H_n / H} = Re{H_n conj(H)} / |H|^2
+                let gd = (re_n * re + im_n * im) / (re.powi(2) + im.powi(2));
+                [freq, gd]
+            })
+            .collect()
+    }
+
+    /// Returns the unwrapped phase in radians of the windowed filter as `[Hz, rad]` pairs at
+    /// `n_points` frequencies from 0 Hz up to the Nyquist frequency.
+    ///
+    /// Jumps of more than `$\pi$` between neighbouring frequencies are removed by adding multiples of
+    /// `$2\pi$`. The sign changes of the response at the stopband zeros remain as jumps of `$\pi$`.
+    pub fn compute_phase_response(&self, n_points: usize) -> Vec<[f64; 2]> {
+        let f = self.compute_filter();
+        let df = self.f_sampling / 2.0 / n_points as f64;
+
+        let mut offset = 0.0;
+        let mut phase_prev = 0.0;
+        (0..n_points)
+            .map(|k| {
+                let freq = k as f64 * df;
+                let (re, im) = response_at(f.iter().copied(), 2.0 * PI * freq / self.f_sampling);
+                let phase = im.atan2(re);
+
+                if k > 0 {
+                    let delta = phase - phase_prev;
+                    if delta > PI {
+                        offset -= 2.0 * PI;
+                    } else if delta < -PI {
+                        offset += 2.0 * PI;
+                    }
+                }
+                phase_prev = phase;
+
+                [freq, phase + offset]
+            })
+            .collect()
+    }
+
+    /// Returns the magnitude, unwrapped phase and group delay of the windowed filter at the
+    /// `n_points` frequencies of [`FilterDef::compute_phase_response`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let def = FilterDef {
+    ///     len: 65,
+    ///     shift: 32,
+    ///     f_sampling: 1000.0,
+    ///     f_hi_cut: 200.0,
+    ///     ..Default::default()
+    /// };
+    /// let table = def.compute_frequency_response_table(64);
+    ///
+    /// assert_eq!(table.len(), 64);
+    /// assert!(table[0].magnitude_db.abs() < 0.1);
+    /// assert!((table[8].group_delay_samples - 32.0).abs() < 1e-6);
+    /// ```
+    pub fn compute_frequency_response_table(&self, n_points: usize) -> Vec<FreqPoint> {
+        let f = self.compute_filter();
+
+        self.compute_phase_response(n_points)
+            .into_iter()
+            .map(|[freq_hz, phase_rad]| {
+                let (re, im) = response_at(f.iter().copied(), 2.0 * PI * freq_hz / self.f_sampling);
+
+                FreqPoint {
+                    freq_hz,
+                    magnitude_db: 10.0 * (re.powi(2) + im.powi(2)).log10(),
+                    phase_rad,
+                    group_delay_samples: FilterDef::compute_group_delay_at(
+                        &f,
+                        freq_hz,
+                        self.f_sampling,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the phase `$-2\pi f s / f_s$` of an ideal delay of `shift` samples as `[Hz, rad]`
+    /// pairs at the frequencies of [`FilterDef::compute_phase_response`].
+    pub fn compute_ideal_linear_phase(
+        shift: usize,
+        f_sampling: f64,
+        n_points: usize,
+    ) -> Vec<[f64; 2]> {
+        let df = f_sampling / 2.0 / n_points as f64;
+
+        (0..n_points)
+            .map(|k| {
+                let freq = k as f64 * df;
+                [freq, -2.0 * PI * freq * shift as f64 / f_sampling]
+            })
+            .collect()
+    }
+
+    /// Divides the coefficients by the gain `g`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let f = [1.0, 2.0, 1.0];
+    /// let f = FilterDef::normalize_filter(&f, FilterDef::compute_dc_gain(&f));
+    /// assert_eq!(f, vec![0.25, 0.5, 0.25]);
+    /// ```
+    pub fn normalize_filter(f: &[f64], g: f64) -> Vec<f64> {
+        f.iter().map(|h| h / g).collect()
+    }
+
+    /// Returns the smallest gain of the windowed filter over the passband, the factor to divide the
+    /// coefficients by for the whole passband to reach at least unity gain.
+    ///
+    /// Normalizing at the band center does not account for the droop of the windowed response
+    /// towards the band edges. The passband edges are taken half the transition width
+    /// `$f_s D / N$` of the window inside the cutoffs, since the ideal cutoff itself lies in the
+    /// middle of the transition band. The gain is evaluated at [`DFT_LEN`] frequencies and at the
+    /// passband center, which is used alone if the passband is narrower than the transition band.
+    pub fn compute_passband_correction_factor(&self) -> f64 {
+        self.compute_passband_correction_factor_of(&self.compute_filter())
+    }
+
+    /// Returns the smallest passband gain of the coefficients `f`, see
+    /// [`FilterDef::compute_passband_correction_factor`].
+    pub fn compute_passband_correction_factor_of(&self, f: &[f64]) -> f64 {
+        let gain = |freq_hz: f64| gain_at(f.iter().copied(), 2.0 * PI * freq_hz / self.f_sampling);
+
+        (0..=DFT_LEN)
+            .map(|k| self.f_sampling / 2.0 * k as f64 / DFT_LEN as f64)
+            .filter(|freq| self.is_passband(*freq) && !self.is_near_band_edge(*freq))
+            .map(gain)
+            .fold(gain(self.passband_center_hz()), f64::min)
+    }
+
+    /// Returns the L2 norm `$\sqrt{\int (|H(f)| - D(f))^2 df}$` of the deviation of the windowed
+    /// magnitude response from the ideal response `$D(f)$`, 1 in the passband and 0 in the stopband,
+    /// evaluated at `n_points` frequencies over `$[0, f_s/2]$`.
+    ///
+    /// The ideal cutoffs lie in the middle of the transition bands, where any windowed filter deviates
+    /// by about one half. The transition bands, half the transition width `$f_s D / N$` of the window
+    /// on either side of a band edge, are therefore excluded from the integral.
+    pub fn frequency_response_l2_error(&self, n_points: usize) -> f64 {
+        self.frequency_response_l2_error_of(&self.compute_filter(), n_points)
+    }
 
-            (re, im)
-        });
+    /// Returns the L2 error of the magnitude response of the coefficients `f`, see
+    /// [`FilterDef::frequency_response_l2_error`].
+    pub fn frequency_response_l2_error_of(&self, f: &[f64], n_points: usize) -> f64 {
+        let df = self.f_sampling / 2.0 / n_points.max(1) as f64;
 
-        (re.powi(2) + im.powi(2)).sqrt()
+        (self
+            .frequency_response_errors(f, n_points)
+            .map(|e| e.powi(2))
+            .sum::<f64>()
+            * df)
+            .sqrt()
     }
 
-    pub fn normalize_filter(f: &Vec<f64>, g: f64) -> Vec<f64> {
-        f.into_iter().map(|h| h / g).collect()
+    /// Returns the L∞ norm, the largest absolute deviation of the windowed magnitude response from
+    /// the ideal response outside the transition bands, see
+    /// [`FilterDef::frequency_response_l2_error`].
+    ///
+    /// In the passband this is the larger of the ripple above and the droop below unity gain, in the
+    /// stopband the height of the largest side lobe.
+    pub fn frequency_response_inf_error(&self, n_points: usize) -> f64 {
+        self.frequency_response_inf_error_of(&self.compute_filter(), n_points)
+    }
+
+    /// Returns the L∞ error of the magnitude response of the coefficients `f`, see
+    /// [`FilterDef::frequency_response_inf_error`].
+    pub fn frequency_response_inf_error_of(&self, f: &[f64], n_points: usize) -> f64 {
+        self.frequency_response_errors(f, n_points)
+            .fold(0.0, f64::max)
+    }
+
+    fn frequency_response_errors<'a>(
+        &'a self,
+        f: &'a [f64],
+        n_points: usize,
+    ) -> impl Iterator<Item = f64> + 'a {
+        let n_points = n_points.max(1);
+
+        (0..n_points)
+            .map(move |k| self.f_sampling / 2.0 * k as f64 / n_points as f64)
+            .filter(|freq| !self.is_near_band_edge(*freq))
+            .map(move |freq| {
+                let gain = gain_at(f.iter().copied(), 2.0 * PI * freq / self.f_sampling);
+                let ideal = if self.is_passband(freq) { 1.0 } else { 0.0 };
+                (gain - ideal).abs()
+            })
+    }
+
+    /// Returns true if the frequency lies within half the transition width `$f_s D / N$` of the
+    /// window from an edge between a pass and a stop band.
+    fn is_near_band_edge(&self, freq_hz: f64) -> bool {
+        let half_transition =
+            self.f_sampling * self.window.characteristic_constant() / self.len.max(1) as f64 / 2.0;
+        let is_passband =
+            |freq_hz: f64| self.is_passband(freq_hz.clamp(0.0, self.f_sampling / 2.0));
+
+        is_passband(freq_hz - half_transition) != is_passband(freq_hz)
+            || is_passband(freq_hz + half_transition) != is_passband(freq_hz)
+    }
+
+    /// Returns the largest change of the passband magnitude response, relative to the passband peak,
+    /// caused by perturbing any single coefficient by `$\pm$perturbation`.
+    ///
+    /// The response is evaluated at `n_points` frequencies from DC to the Nyquist frequency and the
+    /// passband is taken as the frequencies within 3 dB of the peak.
+    pub fn coefficient_sensitivity(
+        coefficients: &[f64],
+        perturbation: f64,
+        n_points: usize,
+    ) -> f64 {
+        let w: Vec<f64> = (0..n_points)
+            .map(|k| PI * k as f64 / (n_points.max(2) - 1) as f64)
+            .collect();
+        let h: Vec<(f64, f64)> = w
+            .iter()
+            .map(|w| response_at(coefficients.iter().copied(), *w))
+            .collect();
+        let magnitude: Vec<f64> = h
+            .iter()
+            .map(|(re, im)| (re.powi(2) + im.powi(2)).sqrt())
+            .collect();
+
+        let peak = magnitude.iter().copied().fold(0.0, f64::max);
+        if peak == 0.0 {
+            return 0.0;
+        }
+
+        let mut sensitivity: f64 = 0.0;
+        for k in 0..coefficients.len() {
+            for (i, w) in w.iter().enumerate() {
+                if magnitude[i] < peak / 2f64.sqrt() {
+                    continue;
+                }
+
+                // Perturbing h[k] adds perturbation * e^{-jwk} to the response
+                let (re, im) = h[i];
+                let (dre, dim) = (
+                    perturbation * (w * k as f64).cos(),
+                    -perturbation * (w * k as f64).sin(),
+                );
+                for sign in [1.0, -1.0] {
+                    let perturbed = ((re + sign * dre).powi(2) + (im + sign * dim).powi(2)).sqrt();
+                    sensitivity = sensitivity.max((perturbed - magnitude[i]).abs());
+                }
+            }
+        }
+
+        sensitivity / peak
+    }
+
+    /// Returns the number of bits, including the sign bit, needed for the coefficients so that
+    /// rounding every coefficient by up to one LSB keeps the worst case passband deviation below
+    /// `tolerance_db`.
+    ///
+    /// The deviation of a single LSB is estimated with [`FilterDef::coefficient_sensitivity`] and
+    /// assumed to add up over all coefficients.
+    pub fn recommended_word_length(coefficients: &[f64], tolerance_db: f64) -> u32 {
+        let peak = coefficients
+            .iter()
+            .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+        if peak == 0.0 {
+            return 1;
+        }
+
+        // Sensitivity per unit perturbation, measured at the LSB of a 16 bit word
+        let lsb_16 = peak * 2f64.powi(-15);
+        let sensitivity = FilterDef::coefficient_sensitivity(coefficients, lsb_16, 256) / lsb_16;
+        let tolerance = 10f64.powf(tolerance_db / 20.0) - 1.0;
+        let lsb = tolerance / (sensitivity * coefficients.len() as f64);
+
+        (1.0 + (peak / lsb).log2().ceil()).clamp(2.0, 64.0) as u32
+    }
+
+    /// Removes the leading and trailing coefficients whose magnitude is below `threshold_fraction`
+    /// of the largest coefficient.
+    ///
+    /// Returns the remaining coefficients together with the index of the first retained one, which
+    /// has to be subtracted from the shift of the filter to keep its delay.
+    pub fn truncate_to_significant_taps(
+        coefficients: &[f64],
+        threshold_fraction: f64,
+    ) -> (Vec<f64>, usize) {
+        let threshold = threshold_fraction
+            * coefficients
+                .iter()
+                .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+        let is_significant = |h: &f64| h.abs() >= threshold;
+
+        let first = coefficients.iter().position(is_significant).unwrap_or(0);
+        let last = coefficients
+            .iter()
+            .rposition(is_significant)
+            .map_or(first, |last| last + 1);
+
+        (coefficients[first..last].to_vec(), first)
+    }
+
+    /// Returns the coefficients whose magnitude is at least `threshold_fraction` of the largest
+    /// coefficient as a [`SparseFir`], dropping the small taps anywhere in the filter rather than
+    /// only at its ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fir_designer::fir::FilterDef;
+    ///
+    /// let sparse = FilterDef::to_sparse(&[0.001, 0.5, 0.0, 1.0, 0.002], 0.01);
+    /// assert_eq!(sparse.tap_count(), 2);
+    /// assert_eq!(sparse.apply(&[1.0, 0.0, 0.0, 0.0]), vec![0.0, 0.5, 0.0, 1.0]);
+    /// ```
+    pub fn to_sparse(coefficients: &[f64], threshold_fraction: f64) -> SparseFir {
+        let threshold = threshold_fraction
+            * coefficients
+                .iter()
+                .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+
+        let mut sparse = SparseFir {
+            indices: Vec::new(),
+            values: Vec::new(),
+            dropped_magnitude: 0.0,
+        };
+        for (n, h) in coefficients.iter().enumerate() {
+            if h.abs() >= threshold && *h != 0.0 {
+                sparse.indices.push(n);
+                sparse.values.push(*h);
+            } else {
+                sparse.dropped_magnitude += h.abs();
+            }
+        }
+        sparse
+    }
+
+    /// Returns the number of sign changes between consecutive coefficients.
+    pub fn count_zero_crossings(f: &[f64]) -> usize {
+        f.windows(2).filter(|h| h[0] * h[1] < 0.0).count()
+    }
+
+    /// Returns the autocorrelation `$R[k] = \sum_n h[n] h[n+k]$` of the filter coefficients as
+    /// `[lag, R[k]]` pairs for lags `$-N+1$` to `$N-1$`.
+    pub fn compute_autocorrelation(f: &[f64]) -> Vec<[f64; 2]> {
+        let len = f.len() as isize;
+
+        (-len + 1..len)
+            .map(|k| {
+                let r = (0..len)
+                    .filter(|n| n + k >= 0 && n + k < len)
+                    .fold(0.0, |r, n| r + f[n as usize] * f[(n + k) as usize]);
+
+                [k as f64, r]
+            })
+            .collect()
+    }
+
+    /// Returns true if an autocorrelation is symmetric about lag 0 to within round-off error.
+    pub fn is_autocorrelation_symmetric(r: &[[f64; 2]]) -> bool {
+        let tol = 1e-9 * r.iter().fold(0.0, |max: f64, [_, r]| max.max(r.abs()));
+
+        r.iter()
+            .zip(r.iter().rev())
+            .all(|([_, a], [_, b])| (a - b).abs() <= tol)
+    }
+
+    /// Returns the estimated 3 dB bandwidth in Hz of a magnitude response given as `[Hz, dB]` pairs.
+    ///
+    /// The bandwidth is the total width of all frequency bins whose magnitude is at most 3 dB below
+    /// the nominal passband gain of 0 dB.
+    pub fn compute_bandwidth_3db(response: &[[f64; 2]]) -> f64 {
+        if response.len() < 2 {
+            return 0.0;
+        }
+
+        let df = response[1][0] - response[0][0];
+        response.iter().filter(|[_, db]| *db >= -3.0).count() as f64 * df
+    }
+
+    /// Returns the peak to peak passband ripple in dB of a magnitude response given as `[Hz, dB]`
+    /// pairs.
+    ///
+    /// The transition region is excluded by trimming each passband from its cutoff edges up to the
+    /// first local maximum of the response.
+    pub fn compute_passband_ripple(&self, response: &[[f64; 2]]) -> f64 {
+        let (min, max) = self
+            .bands(response, true)
+            .flat_map(|(band, lo_edge, hi_edge)| trim_band(band, lo_edge, hi_edge, true))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), [_, db]| {
+                (min.min(*db), max.max(*db))
+            });
+
+        if min.is_finite() && max.is_finite() {
+            max - min
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the minimum stopband attenuation in dB of a magnitude response given as `[Hz, dB]`
+    /// pairs.
+    ///
+    /// The transition region is excluded by trimming each stopband from its cutoff edges up to the
+    /// first null of the response, making this the level of the largest side lobe.
+    pub fn compute_stopband_attenuation(&self, response: &[[f64; 2]]) -> f64 {
+        let max = self
+            .bands(response, false)
+            .flat_map(|(band, lo_edge, hi_edge)| trim_band(band, lo_edge, hi_edge, false))
+            .fold(f64::NEG_INFINITY, |max, [_, db]| max.max(*db));
+
+        -max
+    }
+
+    /// Returns true if the frequency lies in the ideal passband of the filter.
+    pub fn is_passband(&self, f: f64) -> bool {
+        self.filter.is_passband(f, self.f_lo_cut, self.f_hi_cut)
+    }
+
+    /// Splits a magnitude response into its contiguous pass or stop bands.
+    ///
+    /// Each band is returned together with flags marking whether its lower and upper ends border a
+    /// transition region, as opposed to the ends of the response.
+    fn bands<'a>(
+        &'a self,
+        response: &'a [[f64; 2]],
+        passband: bool,
+    ) -> impl Iterator<Item = (&'a [[f64; 2]], bool, bool)> + 'a {
+        let in_band = move |[f, _]: &[f64; 2]| self.is_passband(*f) == passband;
+
+        let mut start = 0;
+        std::iter::from_fn(move || {
+            while start < response.len() && !in_band(&response[start]) {
+                start += 1;
+            }
+            if start >= response.len() {
+                return None;
+            }
+
+            let mut end = start;
+            while end < response.len() && in_band(&response[end]) {
+                end += 1;
+            }
+
+            let band = (&response[start..end], start > 0, end < response.len());
+            start = end;
+            Some(band)
+        })
+    }
+}
+
+/// Designs a multiband filter from `(f_lo, f_hi, is_passband)` bands in Hz.
+///
+/// The sinc coefficients are the sum of the band-pass kernels of all passbands, normalized to a unit
+/// gain at the center of the first passband and multiplied by the window.
+pub fn design_multiband(
+    bands: &[(f64, f64, bool)],
+    len: usize,
+    shift: usize,
+    f_sampling: f64,
+    window: &Window,
+) -> Vec<f64> {
+    FilterDef {
+        filter: Filter::Multiband {
+            bands: bands.to_vec(),
+        },
+        window: window.clone(),
+        len,
+        shift,
+        f_sampling,
+        ..Default::default()
+    }
+    .compute_filter()
+}
+
+/// Designs a linear phase filter of `len` taps approximating a target magnitude response given as
+/// `[Hz, dB]` pairs, e.g. to compensate a measured response.
+///
+/// The target is linearly interpolated onto a DFT grid of at least twice the filter length, clamped
+/// to its first and last value outside of the measured range. The inverse DFT of the zero phase
+/// spectrum is the ideal impulse response, which is delayed by `len / 2` samples and truncated to the
+/// filter length. The result is unwindowed, apply a window with
+/// [`FilterDef::compute_filter_windowed`].
+pub fn design_to_match_response(target: &[[f64; 2]], len: usize, f_sampling: f64) -> Vec<f64> {
+    if target.is_empty() || len == 0 {
+        return vec![0.0; len];
+    }
+
+    let mut target = target.to_vec();
+    target.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    let db_at = |f: f64| {
+        let i = target.partition_point(|[f_target, _]| *f_target < f);
+        match i {
+            0 => target[0][1],
+            _ if i == target.len() => target[i - 1][1],
+            _ => {
+                let ([f0, db0], [f1, db1]) = (target[i - 1], target[i]);
+                db0 + (db1 - db0) * (f - f0) / (f1 - f0)
+            }
+        }
+    };
+
+    let n_fft = (2 * len).next_power_of_two().max(DFT_LEN);
+    let spectrum: Vec<(f64, f64)> = (0..n_fft)
+        .map(|k| {
+            // Mirror the negative frequencies so the impulse response is real and even
+            let k = k.min(n_fft - k);
+            let f = k as f64 * f_sampling / n_fft as f64;
+            (10f64.powf(db_at(f) / 20.0), 0.0)
+        })
+        .collect();
+    let h = dft(&spectrum, true);
+
+    let shift = len / 2;
+    (0..len).map(|n| h[(n + n_fft - shift) % n_fft].0).collect()
+}
+
+/// Returns the design a fraction `t` of the way from `a` to `b`.
+///
+/// The frequencies, window parameters and multiband band edges are interpolated linearly, the length
+/// and shift are rounded to the nearest sample. The filter type, window type, window mode, symmetry,
+/// normalization and delay offset switch over from `a` to `b` at `$t = 0.5$`, as do the window
+/// parameters and bands if the types or band counts differ.
+pub fn interpolate(a: &FilterDef, b: &FilterDef, t: f64) -> FilterDef {
+    let t = t.clamp(0.0, 1.0);
+    let lerp = |a: f64, b: f64| if a == b { a } else { a * (1.0 - t) + b * t };
+    let lerp_usize = |a: usize, b: usize| lerp(a as f64, b as f64).round() as usize;
+    let take_b = t >= 0.5;
+
+    let filter = match (&a.filter, &b.filter) {
+        (Filter::Multiband { bands: bands_a }, Filter::Multiband { bands: bands_b })
+            if bands_a.len() == bands_b.len() =>
+        {
+            Filter::Multiband {
+                bands: bands_a
+                    .iter()
+                    .zip(bands_b)
+                    .map(|(a, b)| {
+                        let is_passband = if take_b { b.2 } else { a.2 };
+                        (lerp(a.0, b.0), lerp(a.1, b.1), is_passband)
+                    })
+                    .collect(),
+            }
+        }
+        _ if take_b => b.filter.clone(),
+        _ => a.filter.clone(),
+    };
+    let window = match (&a.window, &b.window) {
+        (Window::Planck { epsilon: a }, Window::Planck { epsilon: b }) => Window::Planck {
+            epsilon: lerp(*a, *b),
+        },
+        (Window::Dpss { half_bandwidth: a }, Window::Dpss { half_bandwidth: b }) => Window::Dpss {
+            half_bandwidth: lerp(*a, *b),
+        },
+        _ if take_b => b.window.clone(),
+        _ => a.window.clone(),
+    };
+
+    let nearest = if take_b { b } else { a };
+    FilterDef {
+        filter,
+        window,
+        window_mode: nearest.window_mode,
+        len: lerp_usize(a.len, b.len),
+        shift: lerp_usize(a.shift, b.shift),
+        f_sampling: lerp(a.f_sampling, b.f_sampling),
+        f_lo_cut: lerp(a.f_lo_cut, b.f_lo_cut),
+        f_hi_cut: lerp(a.f_hi_cut, b.f_hi_cut),
+        force_symmetry: nearest.force_symmetry,
+        normalization: nearest.normalization,
+        delay_offset: nearest.delay_offset,
+    }
+}
+
+/// Checks that multiband bands, sorted by frequency, neither overlap nor leave gaps between them.
+pub fn validate_bands(bands: &[(f64, f64, bool)]) -> Vec<FilterWarning> {
+    let mut bands = bands.to_vec();
+    bands.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut warnings = Vec::new();
+    for pair in bands.windows(2) {
+        let (f_hi, f_lo) = (pair[0].1, pair[1].0);
+        if f_lo < f_hi && !warnings.contains(&FilterWarning::BandsOverlap) {
+            warnings.push(FilterWarning::BandsOverlap);
+        }
+        if f_lo > f_hi && !warnings.contains(&FilterWarning::BandsNotContiguous) {
+            warnings.push(FilterWarning::BandsNotContiguous);
+        }
+    }
+
+    warnings
+}
+
+/// Returns a linear chirp of `n_samples` sweeping from 0 Hz to the Nyquist frequency, so sample `n`
+/// has an instantaneous frequency of `n / n_samples * f_sampling / 2`.
+pub fn test_chirp(n_samples: usize, f_sampling: f64) -> Vec<f64> {
+    let duration = n_samples as f64 / f_sampling;
+    let rate = f_sampling / 2.0 / duration;
+
+    (0..n_samples)
+        .map(|n| {
+            let t = n as f64 / f_sampling;
+            (PI * rate * t.powi(2)).sin()
+        })
+        .collect()
+}
+
+/// Frequency response of a filter at a single frequency, see
+/// [`FilterDef::compute_frequency_response_table`].
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub struct FreqPoint {
+    pub freq_hz: f64,
+    pub magnitude_db: f64,
+    pub phase_rad: f64,
+    pub group_delay_samples: f64,
+}
+
+/// Streaming filter that processes a signal one sample at a time, keeping the past input samples
+/// in a circular delay line.
+pub struct FilterState {
+    coefficients: Vec<f64>,
+    history: Vec<f64>,
+    pos: usize,
+}
+
+impl FilterState {
+    pub fn new(coefficients: Vec<f64>) -> Self {
+        let history = vec![0.0; coefficients.len()];
+
+        Self {
+            coefficients,
+            history,
+            pos: 0,
+        }
+    }
+
+    /// Pushes a sample into the delay line and returns the next output sample.
+    pub fn process(&mut self, x: f64) -> f64 {
+        let len = self.coefficients.len();
+        if len == 0 {
+            return 0.0;
+        }
+
+        self.pos = (self.pos + 1) % len;
+        self.history[self.pos] = x;
+
+        self.coefficients
+            .iter()
+            .enumerate()
+            .map(|(k, h)| h * self.history[(self.pos + len - k) % len])
+            .sum()
+    }
+
+    /// Swaps the coefficients, keeping as much of the past input as fits the new length.
+    pub fn set_coefficients(&mut self, coefficients: Vec<f64>) {
+        let len = self.history.len();
+        let history = (0..coefficients.len())
+            .map(|k| match len {
+                0 => 0.0,
+                _ if k < len => self.history[(self.pos + len - k) % len],
+                _ => 0.0,
+            })
+            .rev()
+            .collect();
+
+        self.history = history;
+        self.pos = coefficients.len().saturating_sub(1);
+        self.coefficients = coefficients;
+    }
+
+    /// Clears the delay line.
+    pub fn reset(&mut self) {
+        self.history.iter_mut().for_each(|x| *x = 0.0);
+    }
+}
+
+/// Filter keeping only the significant taps of a coefficient vector, see [`FilterDef::to_sparse`].
+pub struct SparseFir {
+    indices: Vec<usize>,
+    values: Vec<f64>,
+    /// Sum of the magnitudes of the dropped taps.
+    dropped_magnitude: f64,
+}
+
+impl SparseFir {
+    /// Returns the number of multiplications per output sample.
+    pub fn tap_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Filters a signal using only the retained taps. The output is truncated to the length of the
+    /// signal, as with [`FilterDef::apply_overlap_add`].
+    pub fn apply(&self, signal: &[f64]) -> Vec<f64> {
+        (0..signal.len())
+            .map(|n| {
+                self.indices
+                    .iter()
+                    .zip(&self.values)
+                    .filter(|(k, _)| **k <= n)
+                    .map(|(k, h)| h * signal[n - k])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Returns the upper bound `$20 \log_{10} \sum |h_{dropped}|$` of the error response introduced
+    /// by the dropped taps, relative to a unity gain passband. At no frequency does the sparse
+    /// response differ from the full one by more than this level, so stopbands well below it may be
+    /// filled in.
+    pub fn error_bound_db(&self) -> f64 {
+        20.0 * self.dropped_magnitude.log10()
+    }
+}
+
+/// Lazy iterator over the coefficients of a [`FilterDef`], see [`FilterDef::coefficients`].
+pub struct Coefficients<'a> {
+    def: &'a FilterDef,
+    gain: f64,
+    n: usize,
+}
+
+impl Iterator for Coefficients<'_> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.n >= self.def.len {
+            return None;
+        }
+
+        let h = self.def.coefficient_at_with_gain(self.n, self.gain);
+        self.n += 1;
+        Some(h)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.def.len - self.n;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> IntoIterator for &'a FilterDef {
+    type Item = f64;
+    type IntoIter = Coefficients<'a>;
+
+    fn into_iter(self) -> Coefficients<'a> {
+        self.coefficients()
+    }
+}
+
+fn c_add(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn c_sub(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn c_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn c_div(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let d = b.0.powi(2) + b.1.powi(2);
+    ((a.0 * b.0 + a.1 * b.1) / d, (a.1 * b.0 - a.0 * b.1) / d)
+}
+
+fn c_abs(a: (f64, f64)) -> f64 {
+    a.0.hypot(a.1)
+}
+
+/// Returns the DFT of a complex signal given as `(re, im)` pairs, or the inverse DFT including the
+/// `$1/N$` scaling if `inverse` is set.
+fn dft(x: &[(f64, f64)], inverse: bool) -> Vec<(f64, f64)> {
+    let len = x.len();
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let scale = if inverse { 1.0 / len as f64 } else { 1.0 };
+    let twiddles: Vec<(f64, f64)> = (0..len)
+        .map(|k| {
+            let theta = 2.0 * PI * k as f64 / len as f64;
+            (theta.cos(), sign * theta.sin())
+        })
+        .collect();
+
+    (0..len)
+        .map(|k| {
+            let (re, im) = x
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (n, (x_re, x_im))| {
+                    let (w_re, w_im) = twiddles[(k * n) % len];
+                    (
+                        re + x_re * w_re - x_im * w_im,
+                        im + x_re * w_im + x_im * w_re,
+                    )
+                });
+
+            (re * scale, im * scale)
+        })
+        .collect()
+}
+
+/// Returns the DTFT `$(\Re H, \Im H)$` of a signal at the angular frequency `w`.
+fn response_at(f: impl Iterator<Item = f64>, w: f64) -> (f64, f64) {
+    let mut n = 0;
+    f.fold((0.0, 0.0), |(mut re, mut im), h| {
+        re += h * (w * n as f64).cos();
+        im -= h * (w * n as f64).sin();
+        n += 1;
+
+        (re, im)
+    })
+}
+
+/// Solves the linear system `$A x = b$` with a symmetric positive definite matrix `A` by Cholesky
+/// decomposition.
+fn solve_cholesky(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+
+    // A = L L^T, L is stored in the lower triangle of A
+    for j in 0..n {
+        let diagonal = (a[j][j] - (0..j).map(|k| a[j][k].powi(2)).sum::<f64>()).sqrt();
+        a[j][j] = diagonal;
+        for i in j + 1..n {
+            a[i][j] = (a[i][j] - (0..j).map(|k| a[i][k] * a[j][k]).sum::<f64>()) / diagonal;
+        }
+    }
+
+    // Forward substitution L y = b, then back substitution L^T x = y
+    for i in 0..n {
+        b[i] = (b[i] - (0..i).map(|k| a[i][k] * b[k]).sum::<f64>()) / a[i][i];
+    }
+    for i in (0..n).rev() {
+        b[i] = (b[i] - (i + 1..n).map(|k| a[k][i] * b[k]).sum::<f64>()) / a[i][i];
+    }
+    b
+}
+
+/// Returns the magnitude of the DTFT of a signal at the angular frequency `w`.
+fn gain_at(f: impl Iterator<Item = f64>, w: f64) -> f64 {
+    let (re, im) = response_at(f, w);
+
+    (re.powi(2) + im.powi(2)).sqrt()
+}
+
+/// Trims the ends of a band that border a transition region up to the first local maximum (passband)
+/// or minimum (stopband) of the response.
+fn trim_band(band: &[[f64; 2]], lo_edge: bool, hi_edge: bool, maxima: bool) -> &[[f64; 2]] {
+    let towards = |from: f64, to: f64| if maxima { to > from } else { to < from };
+
+    let mut lo = 0;
+    let mut hi = band.len() - 1;
+    if lo_edge {
+        while lo < hi && towards(band[lo][1], band[lo + 1][1]) {
+            lo += 1;
+        }
+    }
+    if hi_edge {
+        while hi > lo && towards(band[hi][1], band[hi - 1][1]) {
+            hi -= 1;
+        }
+    }
+
+    &band[lo..=hi]
+}
+
+/// Error returned when text cannot be parsed into a filter or its coefficients.
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParseError(pub String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parameter combinations that cannot produce a filter, see [`FilterDef::validate`].
+#[derive(PartialEq, Clone, Debug)]
+pub enum FilterError {
+    /// The band pass has equal cutoffs, the lower passband of the band stop ends at DC or the low
+    /// pass cuts off at DC. There is no passband gain to normalize the coefficients by.
+    ZeroBandwidth,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroBandwidth => write!(f, "Passband has zero bandwidth, the filter is all zero"),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum FilterWarning {
+    HighCutAboveNyquist,
+    NonLinearPhase,
+    ShortLength,
+    EvenLengthHighPass,
+    BandsOverlap,
+    BandsNotContiguous,
+    TransitionBandTooNarrow { required_len: usize },
+}
+
+impl fmt::Display for FilterWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HighCutAboveNyquist => {
+                write!(f, "High cut frequency is above the Nyquist frequency")
+            }
+            Self::NonLinearPhase => write!(f, "Shift is not half the length, phase is non-linear"),
+            Self::ShortLength => write!(f, "Filter length is very short"),
+            Self::EvenLengthHighPass => write!(
+                f,
+                "Even-length highpass: Type II has inherent Nyquist null. Consider odd length."
+            ),
+            Self::BandsOverlap => write!(f, "Multiband bands overlap"),
+            Self::BandsNotContiguous => write!(f, "Multiband bands leave gaps between them"),
+            Self::TransitionBandTooNarrow { required_len } => write!(
+                f,
+                "Transition band too narrow for this length. Minimum required: {} taps.",
+                required_len
+            ),
+        }
     }
 }
 
-type WindowFn = fn(usize, usize) -> f64;
-
 fn window_rectangular(_n: usize, _len: usize) -> f64 {
     1.0
 }
@@ -159,7 +2495,165 @@ fn window_flat_top(n: usize, len: usize) -> f64 {
         + 0.006947368 * (8.0 * PI * n / len).cos()
 }
 
-#[derive(Default, PartialEq, Clone)]
+/// Returns the Planck-taper window.
+///
+/// The window is 1 in its center and tapers smoothly to 0 over the first and last `$\epsilon N$`
+/// samples:
+///
+/// ```math
+/// w[n] = \left(1 + \exp\left(\frac{\epsilon N}{n} - \frac{\epsilon N}{\epsilon N - n}\right)\right)^{-1}, \quad 0 < n < \epsilon N
+/// ```
+///
+/// with `$w[0] = 0$` and `$w[N - n] = w[n]$`. For `$\epsilon = 0$` it reduces to the rectangular window.
+fn window_planck(n: usize, len: usize, epsilon: f64) -> f64 {
+    let m = n.min(len - n) as f64;
+    let taper = epsilon * len as f64;
+
+    if m >= taper {
+        1.0
+    } else if m == 0.0 {
+        0.0
+    } else {
+        1.0 / (1.0 + (taper / m - taper / (taper - m)).exp())
+    }
+}
+
+/// Returns the `len` samples of a [`Window::Modified`].
+fn window_modified(
+    base: &Window,
+    modifier: &WindowModifier,
+    mode: WindowMode,
+    len: usize,
+) -> Vec<f64> {
+    let samples = |window: &Window, len: usize| {
+        let window_fn = window.function(mode);
+        (0..len).map(|n| window_fn(n, len)).collect::<Vec<f64>>()
+    };
+
+    match modifier {
+        WindowModifier::Squared => samples(base, len).iter().map(|w| w.powi(2)).collect(),
+        WindowModifier::Sqrt => samples(base, len)
+            .iter()
+            .map(|w| w.signum() * w.abs().sqrt())
+            .collect(),
+        WindowModifier::Cube => samples(base, len).iter().map(|w| w.powi(3)).collect(),
+        WindowModifier::Convolved(other) => {
+            let len_base = len.div_ceil(2);
+            let w = convolve(
+                &samples(base, len_base),
+                &samples(other, len + 1 - len_base),
+            );
+            let peak = w.iter().fold(0.0, |peak: f64, w| peak.max(w.abs()));
+            if peak == 0.0 {
+                return w;
+            }
+
+            FilterDef::normalize_filter(&w, peak)
+        }
+    }
+}
+
+/// Returns the leading discrete prolate spheroidal sequence (Slepian) window of `$N = len + 1$`
+/// samples for the half bandwidth `$W$` in normalized frequency units `$(0, 0.5)$`.
+///
+/// The window maximizes the fraction of its energy within `$|f| < W$` and is the eigenvector of the
+/// largest eigenvalue of the tridiagonal matrix
+///
+/// ```math
+/// T_{i,i} = \left(\frac{N - 1 - 2i}{2}\right)^2 \cos(2\pi W), \quad
+/// T_{i,i-1} = T_{i-1,i} = \frac{i(N - i)}{2}
+/// ```
+///
+/// The eigenvalue is located by bisection using Sturm sequence counts and the eigenvector by inverse
+/// power iteration. The window is scaled to a unit peak.
+///
+/// [\[1\]](https://doi.org/10.1002/j.1538-7305.1978.tb02104.x) David Slepian.
+/// Prolate Spheroidal Wave Functions, Fourier Analysis, and Uncertainty - V: The Discrete Case.
+/// Bell System Technical Journal. 1978.
+fn window_dpss(len: usize, half_bandwidth: f64) -> Vec<f64> {
+    let n_points = len + 1;
+    let cos_w = (2.0 * PI * half_bandwidth).cos();
+    let diag: Vec<f64> = (0..n_points)
+        .map(|i| ((n_points as f64 - 1.0 - 2.0 * i as f64) / 2.0).powi(2) * cos_w)
+        .collect();
+    let off: Vec<f64> = (0..n_points)
+        .map(|i| (i * (n_points - i)) as f64 / 2.0)
+        .collect();
+
+    // Number of eigenvalues below x
+    let sturm_count = |x: f64| {
+        let mut count = 0;
+        let mut q = 1.0;
+        for i in 0..n_points {
+            q = diag[i] - x - if i > 0 { off[i].powi(2) / q } else { 0.0 };
+            if q == 0.0 {
+                q = f64::EPSILON * (off[i].abs() + 1.0);
+            }
+            if q < 0.0 {
+                count += 1;
+            }
+        }
+        count
+    };
+
+    // Gershgorin bounds on the eigenvalues
+    let radius = |i: usize| off[i] + off.get(i + 1).copied().unwrap_or(0.0);
+    let mut lo = (0..n_points).fold(f64::INFINITY, |lo, i| lo.min(diag[i] - radius(i)));
+    let mut hi = (0..n_points).fold(f64::NEG_INFINITY, |hi, i| hi.max(diag[i] + radius(i)));
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if sturm_count(mid) < n_points {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    // Inverse iteration just above the largest eigenvalue, T - sigma I is then negative definite and
+    // the tridiagonal solve is stable without pivoting
+    let sigma = hi + f64::EPSILON.sqrt() * hi.abs().max(1.0);
+    let mut v = vec![1.0; n_points];
+    for _ in 0..4 {
+        let mut c = vec![0.0; n_points];
+        let mut y = vec![0.0; n_points];
+        for i in 0..n_points {
+            let sub = if i > 0 { off[i] } else { 0.0 };
+            let m = diag[i] - sigma - sub * if i > 0 { c[i - 1] } else { 0.0 };
+            c[i] = off.get(i + 1).copied().unwrap_or(0.0) / m;
+            y[i] = (v[i] - sub * if i > 0 { y[i - 1] } else { 0.0 }) / m;
+        }
+        for i in (0..n_points - 1).rev() {
+            y[i] -= c[i] * y[i + 1];
+        }
+
+        let peak = y.iter().fold(0.0, |peak: f64, y| peak.max(y.abs()));
+        let sign = y.iter().sum::<f64>().signum();
+        v = y.iter().map(|y| sign * y / peak).collect();
+    }
+
+    v
+}
+
+/// Window function tapering the sinc coefficients.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::{FilterDef, Window};
+///
+/// let def = FilterDef {
+///     window: Window::Planck { epsilon: 0.25 },
+///     len: 33,
+///     ..Default::default()
+/// };
+///
+/// // The Planck-taper window is flat over its center
+/// assert!(def.compute_window()[8..=24].iter().all(|w| *w == 1.0));
+/// ```
+#[derive(Default, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Window {
     #[default]
     Rectangular,
@@ -173,22 +2667,216 @@ pub enum Window {
     BlackmanNuttall,
     BlackmanHarris,
     FlatTop,
+    Planck {
+        epsilon: f64,
+    },
+    Dpss {
+        half_bandwidth: f64,
+    },
+    /// A window derived from the samples of another window.
+    Modified {
+        base: Box<Window>,
+        modifier: WindowModifier,
+    },
+}
+
+/// Modification of the samples of the base window of a [`Window::Modified`].
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
+pub enum WindowModifier {
+    /// `$w[n]^2$`, e.g. the squared Hann window is the Hann-Poisson like `$\cos^4$` window.
+    Squared,
+    /// `$\sqrt{w[n]}$`, keeping the sign of negative samples.
+    Sqrt,
+    /// `$w[n]^3$`.
+    Cube,
+    /// The discrete convolution of the base window and another window, both of about half the
+    /// length so that the result has the length of the filter, scaled to a unit peak. The
+    /// convolution of two rectangular windows is the triangular window.
+    Convolved(Box<Window>),
 }
 
 impl Window {
-    fn function(&self) -> WindowFn {
+    /// Multiplies the filter by the window, then scales the result to a unit peak amplitude.
+    pub fn apply_and_normalize(f: &[f64], w: &[f64]) -> Vec<f64> {
+        let f_windowed = FilterDef::compute_filter_windowed(f, w);
+        let peak = f_windowed
+            .iter()
+            .fold(0.0, |peak: f64, h| peak.max(h.abs()));
+
+        if peak == 0.0 {
+            return f_windowed;
+        }
+
+        FilterDef::normalize_filter(&f_windowed, peak)
+    }
+
+    /// Returns the normalized transition width `D = N * Δf / f_sampling` of a windowed-sinc filter of
+    /// `N` taps designed with the window.
+    ///
+    /// Values for the rectangular, Hann, Hamming and Blackman windows are the standard table values,
+    /// the remaining windows are approximated from their main lobe width.
+    pub fn characteristic_constant(&self) -> f64 {
+        match self {
+            Self::Rectangular => 0.9,
+            Self::Triangular => 3.05,
+            Self::Welch => 1.8,
+            Self::Sin => 2.0,
+            Self::Hann => 3.1,
+            Self::Hamming => 3.3,
+            Self::Blackman => 5.5,
+            Self::Nuttall => 7.0,
+            Self::BlackmanNuttall => 7.0,
+            Self::BlackmanHarris => 7.0,
+            Self::FlatTop => 9.5,
+            // Tapers from the rectangular window towards a Hann like window as epsilon grows
+            Self::Planck { epsilon } => 0.9 + 4.4 * epsilon.clamp(0.0, 0.5),
+            // The transition width is set by the half bandwidth rather than the length, this is
+            // comparable to a Blackman window for the common time-bandwidth products of 2.5 to 4
+            Self::Dpss { .. } => 5.5,
+            // Rough estimates, powers above one narrow the window and widen its main lobe. Each
+            // half length window of a convolution has twice the main lobe width, the product of
+            // their spectra the narrower of the two
+            Self::Modified { base, modifier } => match modifier {
+                WindowModifier::Squared => 1.4 * base.characteristic_constant(),
+                WindowModifier::Sqrt => 0.7 * base.characteristic_constant(),
+                WindowModifier::Cube => 1.7 * base.characteristic_constant(),
+                WindowModifier::Convolved(other) => {
+                    2.0 * f64::min(
+                        base.characteristic_constant(),
+                        other.characteristic_constant(),
+                    )
+                }
+            },
+        }
+    }
+
+    /// Returns the window function `w(n, len)` for sample `n` of a window of `len` samples.
+    ///
+    /// Each window gets its own closure capturing only what it needs, the parameters of parametric
+    /// windows or the cache of the windows computed as a whole.
+    fn function(&self, mode: WindowMode) -> Box<dyn Fn(usize, usize) -> f64 + '_> {
+        match self {
+            Self::Rectangular => periodic_window(mode, window_rectangular),
+            Self::Triangular => periodic_window(mode, window_triangular),
+            Self::Welch => periodic_window(mode, window_welch),
+            Self::Sin => periodic_window(mode, window_sin),
+            Self::Hann => periodic_window(mode, window_hann),
+            Self::Hamming => periodic_window(mode, window_hamming),
+            Self::Blackman => periodic_window(mode, window_blackman),
+            Self::Nuttall => periodic_window(mode, window_nuttall),
+            Self::BlackmanNuttall => periodic_window(mode, window_blackman_nuttall),
+            Self::BlackmanHarris => periodic_window(mode, window_blackman_harris),
+            Self::FlatTop => periodic_window(mode, window_flat_top),
+            Self::Planck { epsilon } => {
+                let epsilon = *epsilon;
+                periodic_window(mode, move |n, len| window_planck(n, len, epsilon))
+            }
+            Self::Dpss { half_bandwidth } => {
+                let half_bandwidth = *half_bandwidth;
+                // The window is computed over a period, one sample longer than needed when periodic
+                cached_window(move |len| window_dpss(mode.period(len), half_bandwidth))
+            }
+            Self::Modified { base, modifier } => {
+                cached_window(move |len| window_modified(base, modifier, mode, len))
+            }
+        }
+    }
+}
+
+/// Wraps a window formula written in terms of the period of the window.
+fn periodic_window<'a>(
+    mode: WindowMode,
+    window_fn: impl Fn(usize, usize) -> f64 + 'a,
+) -> Box<dyn Fn(usize, usize) -> f64 + 'a> {
+    Box::new(move |n, len| {
+        // A single tap window, the window formulas would divide by zero
+        if len <= 1 {
+            return 1.0;
+        }
+
+        window_fn(n, mode.period(len))
+    })
+}
+
+/// Wraps a window computed as a whole for a given length, keeping the last solution around.
+fn cached_window<'a>(
+    compute: impl Fn(usize) -> Vec<f64> + 'a,
+) -> Box<dyn Fn(usize, usize) -> f64 + 'a> {
+    let samples: RefCell<Option<(usize, Vec<f64>)>> = RefCell::new(None);
+
+    Box::new(move |n, len| {
+        if len <= 1 {
+            return 1.0;
+        }
+
+        let mut samples = samples.borrow_mut();
+        if samples.as_ref().is_none_or(|(l, _)| *l != len) {
+            *samples = Some((len, compute(len)));
+        }
+        samples.as_ref().map_or(1.0, |(_, w)| w[n])
+    })
+}
+
+/// Selects the period of the window relative to its length `$N$`.
+#[derive(Default, Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum WindowMode {
+    /// The window has a period of `$N - 1$` and is symmetric about its center, e.g. the Hann window
+    /// is zero at both ends. This is the usual choice for filter design.
+    #[default]
+    Symmetric,
+    /// The window has a period of `$N$`, as if it were the first `$N$` samples of a symmetric window
+    /// of `$N + 1$` samples. This is the usual choice for spectral analysis.
+    Periodic,
+}
+
+impl WindowMode {
+    fn period(&self, len: usize) -> usize {
+        match self {
+            Self::Symmetric => len - 1,
+            Self::Periodic => len,
+        }
+    }
+}
+
+/// Selects how the windowed filter coefficients are scaled.
+#[derive(Default, Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Normalization {
+    /// Unit gain at DC or at the center of the passband, depending on the filter type.
+    #[default]
+    UnitPassbandGain,
+    /// Unit energy `$\sum_n h[n]^2 = 1$`, see [`FilterDef::normalize_to_unit_energy`].
+    UnitEnergy,
+    /// Coefficients adding up to one, i.e. unit gain at DC.
+    UnitSum,
+}
+
+impl Normalization {
+    /// Returns the factor to divide the coefficients by, which are already normalized to unit
+    /// passband gain for [`Normalization::UnitPassbandGain`].
+    fn scale(&self, f: &[f64]) -> f64 {
+        match self {
+            Self::UnitPassbandGain => 1.0,
+            Self::UnitEnergy => f.iter().map(|h| h.powi(2)).sum::<f64>().sqrt(),
+            Self::UnitSum => FilterDef::compute_dc_gain(f),
+        }
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnitPassbandGain => write!(f, "Unit Passband Gain"),
+            Self::UnitEnergy => write!(f, "Unit Energy"),
+            Self::UnitSum => write!(f, "Unit Sum"),
+        }
+    }
+}
+
+impl fmt::Display for WindowMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Rectangular => window_rectangular,
-            Self::Triangular => window_triangular,
-            Self::Welch => window_welch,
-            Self::Sin => window_sin,
-            Self::Hann => window_hann,
-            Self::Hamming => window_hamming,
-            Self::Blackman => window_blackman,
-            Self::Nuttall => window_nuttall,
-            Self::BlackmanNuttall => window_blackman_nuttall,
-            Self::BlackmanHarris => window_blackman_harris,
-            Self::FlatTop => window_flat_top,
+            Self::Symmetric => write!(f, "Symmetric"),
+            Self::Periodic => write!(f, "Periodic"),
         }
     }
 }
@@ -207,78 +2895,330 @@ impl fmt::Display for Window {
             Self::BlackmanNuttall => write!(f, "Blackman Nutall"),
             Self::BlackmanHarris => write!(f, "Blackman Harris"),
             Self::FlatTop => write!(f, "Flat Top"),
+            Self::Planck { .. } => write!(f, "Planck"),
+            Self::Dpss { .. } => write!(f, "DPSS"),
+            Self::Modified { base, modifier } => match modifier {
+                WindowModifier::Squared => write!(f, "{}²", base),
+                WindowModifier::Sqrt => write!(f, "√{}", base),
+                WindowModifier::Cube => write!(f, "{}³", base),
+                WindowModifier::Convolved(other) => write!(f, "{} ∗ {}", base, other),
+            },
+        }
+    }
+}
+
+impl fmt::Display for WindowModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Squared => write!(f, "Squared"),
+            Self::Sqrt => write!(f, "Square Root"),
+            Self::Cube => write!(f, "Cube"),
+            Self::Convolved(_) => write!(f, "Convolved"),
         }
     }
 }
 
-type FilterFn = fn(usize, usize, f64, f64, f64) -> f64;
+/// Returns the linear convolution `$(a * b)[n] = \sum_k a[k] b[n-k]$` of two sequences, computed
+/// with the direct sum.
+///
+/// The output has `a.len() + b.len() - 1` samples, or none if either input is empty.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::convolve;
+///
+/// assert_eq!(convolve(&[1.0, 2.0], &[1.0, 1.0, 1.0]), vec![1.0, 3.0, 3.0, 2.0]);
+/// // A unit impulse returns a copy of the other operand
+/// assert_eq!(convolve(&[1.0], &[0.5, -0.5]), vec![0.5, -0.5]);
+/// assert!(convolve(&[], &[1.0]).is_empty());
+/// ```
+pub fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
 
-fn filter_low_pass(n: usize, shift: usize, dt: f64, _f_lo_cut: f64, f_hi_cut: f64) -> f64 {
-    let n = n as f64;
-    let shift = shift as f64;
+    let mut y = vec![0.0; a.len() + b.len() - 1];
+    for (n, a_n) in a.iter().enumerate() {
+        for (k, b_k) in b.iter().enumerate() {
+            y[n + k] += a_n * b_k;
+        }
+    }
+    y
+}
 
-    if n != shift {
-        (2.0 * PI * f_hi_cut * dt * (n - shift)).sin() / (PI * dt * (n - shift))
+/// Returns the normalized sinc function `$\mathrm{sinc}(x) = \frac{\sin(\pi x)}{\pi x}$` with
+/// `$\mathrm{sinc}(0) = 1$`.
+pub fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
     } else {
-        2.0 * f_hi_cut
+        (PI * x).sin() / (PI * x)
     }
 }
 
+/// Returns the ideal lowpass impulse response `$2 f_c \, \mathrm{sinc}(2 f_c \Delta t (n - s))$`.
+fn filter_low_pass(n: usize, shift: usize, dt: f64, _f_lo_cut: f64, f_hi_cut: f64) -> f64 {
+    let m = n as f64 - shift as f64;
+
+    2.0 * f_hi_cut * sinc(2.0 * f_hi_cut * dt * m)
+}
+
+/// Returns an all pass, a lowpass with its cutoff at the Nyquist frequency, minus a lowpass.
 fn filter_high_pass(n: usize, shift: usize, dt: f64, f_lo_cut: f64, _f_hi_cut: f64) -> f64 {
-    let n = n as f64;
-    let shift = shift as f64;
+    let m = n as f64 - shift as f64;
 
-    if n != shift {
-        ((PI * (n - shift)).sin() - (2.0 * PI * f_lo_cut * dt * (n - shift)).sin())
-            / (PI * dt * (n - shift))
-    } else {
-        1.0 / dt - 2.0 * f_lo_cut
-    }
+    sinc(m) / dt - filter_low_pass(n, shift, dt, 0.0, f_lo_cut)
 }
 
 fn filter_band_pass(n: usize, shift: usize, dt: f64, f_lo_cut: f64, f_hi_cut: f64) -> f64 {
-    let n = n as f64;
-    let shift = shift as f64;
+    filter_low_pass(n, shift, dt, 0.0, f_hi_cut) - filter_low_pass(n, shift, dt, 0.0, f_lo_cut)
+}
 
-    if n != shift {
-        ((2.0 * PI * f_hi_cut * dt * (n - shift)).sin()
-            - (2.0 * PI * f_lo_cut * dt * (n - shift)).sin())
-            / (PI * dt * (n - shift))
-    } else {
-        2.0 * f_hi_cut - 2.0 * f_lo_cut
-    }
+fn filter_multiband(n: usize, shift: usize, dt: f64, bands: &[(f64, f64, bool)]) -> f64 {
+    bands
+        .iter()
+        .filter(|(_, _, is_passband)| *is_passband)
+        .map(|(f_lo_cut, f_hi_cut, _)| filter_band_pass(n, shift, dt, *f_lo_cut, *f_hi_cut))
+        .sum()
 }
 
 fn filter_band_stop(n: usize, shift: usize, dt: f64, f_lo_cut: f64, f_hi_cut: f64) -> f64 {
-    let n = n as f64;
-    let shift = shift as f64;
+    let m = n as f64 - shift as f64;
 
-    if n != shift {
-        ((2.0 * PI * f_lo_cut * dt * (n - shift)).sin()
-            - (2.0 * PI * f_hi_cut * dt * (n - shift)).sin()
-            + (PI * (n - shift)).sin())
-            / (PI * dt * (n - shift))
-    } else {
-        2.0 * f_lo_cut - 2.0 * f_hi_cut + 1.0 / dt
+    sinc(m) / dt - filter_band_pass(n, shift, dt, f_lo_cut, f_hi_cut)
+}
+
+/// Expression of a [`Filter::Custom`] kernel with the raw coefficients of [`Filter::LowPass`].
+pub const DEFAULT_CUSTOM_EXPRESSION: &str = "2 * f_hi_cut * sinc(2 * f_hi_cut * dt * (n - shift))";
+
+/// Compiles the expression of a [`Filter::Custom`] kernel into a function of
+/// `(n, shift, dt, f_lo_cut, f_hi_cut)` returning the raw coefficient at tap `n`.
+///
+/// The expression may use the float variables `n`, `shift`, `dt`, `f_lo_cut`, `f_hi_cut` and `PI`,
+/// the functions `sin`, `cos`, `tan`, `exp`, `ln`, `sqrt`, `abs` and the normalized [`sinc`], as
+/// well as all builtins of [`evalexpr`]. Note that operations on integer literals stay integer, i.e.
+/// `1 / 2` is `0` while `1.0 / 2` is `0.5`.
+///
+/// Taps where the expression is not finite, e.g. `0 / 0` at `n = shift`, are replaced by the mean of
+/// the expression evaluated just before and after the tap. Errors during evaluation yield `0`.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::{compile_custom_filter, DEFAULT_CUSTOM_EXPRESSION};
+///
+/// let h = compile_custom_filter(DEFAULT_CUSTOM_EXPRESSION).unwrap();
+/// assert!((h(32, 32, 1e-3, 0.0, 300.0) - 600.0).abs() < 1e-9);
+///
+/// assert!(compile_custom_filter("sin(n").is_err());
+/// assert!(compile_custom_filter("n * unknown").is_err());
+/// ```
+pub fn compile_custom_filter(
+    expression: &str,
+) -> Result<impl Fn(usize, usize, f64, f64, f64) -> f64, ParseError> {
+    let node = build_operator_tree(expression).map_err(|e| ParseError(e.to_string()))?;
+    let eval = move |n: f64, shift: usize, dt: f64, f_lo_cut: f64, f_hi_cut: f64| {
+        let context = KernelContext {
+            variables: [
+                Value::Float(n),
+                Value::Float(shift as f64),
+                Value::Float(dt),
+                Value::Float(f_lo_cut),
+                Value::Float(f_hi_cut),
+                Value::Float(PI),
+            ],
+        };
+        node.eval_number_with_context(&context)
+    };
+
+    // Catch unknown identifiers and non numeric results up front
+    eval(0.0, 1, 1e-3, 100.0, 200.0).map_err(|e| ParseError(e.to_string()))?;
+
+    Ok(move |n, shift, dt, f_lo_cut, f_hi_cut| {
+        let h = |n| eval(n, shift, dt, f_lo_cut, f_hi_cut).unwrap_or(0.0);
+
+        match h(n as f64) {
+            h_n if h_n.is_finite() => h_n,
+            _ => (h(n as f64 - 1e-6) + h(n as f64 + 1e-6)) / 2.0,
+        }
+    })
+}
+
+/// Variables and functions available to the expression of a [`Filter::Custom`] kernel.
+struct KernelContext {
+    variables: [Value; 6],
+}
+
+impl KernelContext {
+    const VARIABLES: [&'static str; 6] = ["n", "shift", "dt", "f_lo_cut", "f_hi_cut", "PI"];
+}
+
+impl Context for KernelContext {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        let i = Self::VARIABLES.iter().position(|v| *v == identifier)?;
+        Some(&self.variables[i])
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        let f: fn(f64) -> f64 = match identifier {
+            "sin" => f64::sin,
+            "cos" => f64::cos,
+            "tan" => f64::tan,
+            "exp" => f64::exp,
+            "ln" => f64::ln,
+            "sqrt" => f64::sqrt,
+            "abs" => f64::abs,
+            "sinc" => sinc,
+            _ => {
+                return Err(EvalexprError::FunctionIdentifierNotFound(
+                    identifier.to_string(),
+                ))
+            }
+        };
+
+        Ok(Value::Float(f(argument.as_number()?)))
+    }
+
+    fn are_builtin_functions_disabled(&self) -> bool {
+        false
+    }
+
+    fn set_builtin_functions_disabled(&mut self, disabled: bool) -> EvalexprResult<()> {
+        if disabled {
+            Err(EvalexprError::CustomMessage(
+                "builtin functions are always enabled".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
     }
 }
 
-#[derive(Default, PartialEq, Clone)]
+/// Ideal frequency response the sinc coefficients approximate.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::{Filter, FilterDef};
+///
+/// let def = FilterDef {
+///     filter: Filter::BandStop,
+///     len: 101,
+///     shift: 50,
+///     f_sampling: 1000.0,
+///     f_lo_cut: 100.0,
+///     f_hi_cut: 200.0,
+///     ..Default::default()
+/// };
+///
+/// // A bandstop filter passes DC
+/// assert!((FilterDef::compute_dc_gain(&def.compute_filter()) - 1.0).abs() < 0.01);
+/// assert!(def.is_passband(0.0) && !def.is_passband(150.0));
+/// ```
+#[derive(Default, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Filter {
     #[default]
     LowPass,
     HighPass,
     BandPass,
     BandStop,
+    /// Any number of `(f_lo, f_hi, is_passband)` bands in Hz.
+    Multiband {
+        bands: Vec<(f64, f64, bool)>,
+    },
+    /// Kernel given by an expression of the tap index, see [`compile_custom_filter`]. Its passband
+    /// is taken to be the band between the low and high cutoff.
+    Custom {
+        expression: String,
+    },
+    /// Kernel implemented by a library user, see [`FilterKernel`]. Like custom expressions, its
+    /// passband is taken to be the band between the low and high cutoff. Kernels cannot be
+    /// serialized or selected in the GUI.
+    #[serde(skip)]
+    Kernel(Arc<dyn FilterKernel + Send + Sync>),
+}
+
+/// Raw filter coefficients provided by a library user through [`Filter::Kernel`].
+///
+/// The coefficients are normalized to unit gain at the passband center and windowed like those of
+/// the built-in filters.
+///
+/// # Examples
+///
+/// ```
+/// use fir_designer::fir::{sinc, Filter, FilterDef, FilterKernel};
+/// use std::sync::Arc;
+///
+/// /// Lowpass whose frequency response is a triangle reaching zero at the high cutoff.
+/// struct SincSquaredKernel;
+///
+/// impl FilterKernel for SincSquaredKernel {
+///     fn value(&self, n: usize, shift: usize, dt: f64, _f_lo_cut: f64, f_hi_cut: f64) -> f64 {
+///         let m = n as f64 - shift as f64;
+///         f_hi_cut * sinc(f_hi_cut * dt * m).powi(2)
+///     }
+/// }
+///
+/// let def = FilterDef {
+///     filter: Filter::Kernel(Arc::new(SincSquaredKernel)),
+///     len: 129,
+///     shift: 64,
+///     f_sampling: 1000.0,
+///     f_hi_cut: 200.0,
+///     ..Default::default()
+/// };
+/// let f = def.compute_filter();
+///
+/// // Every sample of a squared sinc is non-negative and the filter is symmetric
+/// assert!(f.iter().all(|h| *h >= 0.0));
+/// assert!((f[0] - f[128]).abs() < 1e-12);
+/// ```
+pub trait FilterKernel {
+    /// Returns the raw coefficient of tap `n` with the same parameters as the built-in kernels, the
+    /// shift and sample interval `dt` in samples and seconds, and the cutoffs in Hz.
+    fn value(&self, n: usize, shift: usize, dt: f64, f_lo_cut: f64, f_hi_cut: f64) -> f64;
+}
+
+/// Kernels are compared by identity, two filters are only equal if they share the same kernel.
+impl PartialEq for dyn FilterKernel + Send + Sync {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
 }
 
 impl Filter {
-    fn function(&self) -> FilterFn {
+    /// Returns true if the frequency lies in the ideal passband of the filter.
+    pub fn is_passband(&self, f: f64, f_lo_cut: f64, f_hi_cut: f64) -> bool {
+        match self {
+            Self::LowPass => f <= f_hi_cut,
+            Self::HighPass => f >= f_lo_cut,
+            Self::BandPass => f >= f_lo_cut && f <= f_hi_cut,
+            Self::BandStop => f <= f_lo_cut || f >= f_hi_cut,
+            Self::Multiband { bands } => bands
+                .iter()
+                .any(|(f_lo, f_hi, is_passband)| *is_passband && f >= *f_lo && f <= *f_hi),
+            Self::Custom { .. } | Self::Kernel(_) => f >= f_lo_cut && f <= f_hi_cut,
+        }
+    }
+
+    /// Returns the kernel `h(n, shift, dt, f_lo_cut, f_hi_cut)` of the filter.
+    fn function(&self) -> Box<dyn Fn(usize, usize, f64, f64, f64) -> f64 + '_> {
         match self {
-            Self::LowPass => filter_low_pass,
-            Self::HighPass => filter_high_pass,
-            Self::BandPass => filter_band_pass,
-            Self::BandStop => filter_band_stop,
+            Self::LowPass => Box::new(filter_low_pass),
+            Self::HighPass => Box::new(filter_high_pass),
+            Self::BandPass => Box::new(filter_band_pass),
+            Self::BandStop => Box::new(filter_band_stop),
+            Self::Multiband { bands } => {
+                Box::new(move |n, shift, dt, _, _| filter_multiband(n, shift, dt, bands))
+            }
+            Self::Custom { expression } => match compile_custom_filter(expression) {
+                Ok(h) => Box::new(h),
+                Err(_) => Box::new(|_, _, _, _, _| 0.0),
+            },
+            Self::Kernel(kernel) => Box::new(move |n, shift, dt, f_lo_cut, f_hi_cut| {
+                kernel.value(n, shift, dt, f_lo_cut, f_hi_cut)
+            }),
         }
     }
 }
@@ -290,6 +3230,648 @@ impl fmt::Display for Filter {
             Self::HighPass => write!(f, "High Pass"),
             Self::BandPass => write!(f, "Band Pass"),
             Self::BandStop => write!(f, "Band Stop"),
+            Self::Multiband { .. } => write!(f, "Multiband"),
+            Self::Custom { .. } => write!(f, "Custom"),
+            Self::Kernel(_) => write!(f, "Kernel"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn dpss_window_follows_window_mode() {
+        let symmetric = FilterDef {
+            window: Window::Dpss {
+                half_bandwidth: 0.1,
+            },
+            len: 33,
+            shift: 16,
+            ..Default::default()
+        };
+        let w = symmetric.compute_window();
+        for n in 0..w.len() {
+            assert!((w[n] - w[w.len() - 1 - n]).abs() < 1e-9);
+        }
+
+        let periodic = FilterDef {
+            window_mode: WindowMode::Periodic,
+            ..symmetric
+        };
+        let w_periodic = periodic.compute_window();
+        assert_eq!(w_periodic.len(), 33);
+        assert!((w_periodic[1] - w_periodic[32]).abs() < 1e-9);
+        assert!((w_periodic[0] - w_periodic[32]).abs() > 1e-6);
+    }
+
+    #[test]
+    fn equalize_group_delay_keeps_taps_after_peak() {
+        let def = FilterDef {
+            window: Window::Hamming,
+            len: 33,
+            shift: 16,
+            f_sampling: 1000.0,
+            f_hi_cut: 200.0,
+            force_symmetry: true,
+            ..Default::default()
+        };
+        let f = def.compute_filter();
+
+        for target in [10, 16, 25] {
+            let mut delayed = def.clone();
+            delayed.equalize_group_delay(target);
+            let f_delayed = delayed.compute_filter();
+
+            assert_eq!(delayed.shift, target);
+            assert_eq!(f_delayed.len(), delayed.len);
+            assert_eq!(f_delayed[target..], f[def.shift..]);
+            let peak = f_delayed.iter().copied().fold(f64::MIN, f64::max);
+            assert!((delayed.coefficient_at(target) - peak).abs() < 1e-12);
+            for (n, h) in f_delayed.iter().enumerate() {
+                assert!((delayed.coefficient_at(n) - h).abs() < 1e-12);
+            }
+        }
+    }
+
+    /// Returns the raw coefficients of the filter times `dt`, scaled so the all pass is a unit impulse.
+    fn raw_coefficients(def: &FilterDef) -> Vec<f64> {
+        (0..def.len)
+            .map(|n| def.filter_at(n) / def.f_sampling)
+            .collect()
+    }
+
+    fn assert_unit_impulse(f: &[f64], shift: usize) {
+        for (n, h) in f.iter().enumerate() {
+            let expected = if n == shift { 1.0 } else { 0.0 };
+            assert!((h - expected).abs() < 1e-10, "h[{}] = {}", n, h);
+        }
+    }
+
+    #[test]
+    fn band_stop_complements_band_pass() {
+        let band_pass = FilterDef {
+            filter: Filter::BandPass,
+            len: 65,
+            shift: 32,
+            f_sampling: 1000.0,
+            f_lo_cut: 100.0,
+            f_hi_cut: 300.0,
+            ..Default::default()
+        };
+        let band_stop = FilterDef {
+            filter: Filter::BandStop,
+            ..band_pass.clone()
+        };
+
+        let f: Vec<f64> = raw_coefficients(&band_pass)
+            .iter()
+            .zip(raw_coefficients(&band_stop))
+            .map(|(h_bp, h_bs)| h_bp + h_bs)
+            .collect();
+        assert_unit_impulse(&f, band_pass.shift);
+    }
+
+    #[test]
+    fn high_pass_complements_low_pass() {
+        let low_pass = FilterDef {
+            filter: Filter::LowPass,
+            len: 64,
+            shift: 20,
+            f_sampling: 1000.0,
+            f_lo_cut: 150.0,
+            f_hi_cut: 150.0,
+            ..Default::default()
+        };
+        let high_pass = FilterDef {
+            filter: Filter::HighPass,
+            ..low_pass.clone()
+        };
+
+        let f: Vec<f64> = raw_coefficients(&low_pass)
+            .iter()
+            .zip(raw_coefficients(&high_pass))
+            .map(|(h_lp, h_hp)| h_lp + h_hp)
+            .collect();
+        assert_unit_impulse(&f, low_pass.shift);
+    }
+
+    #[test]
+    fn center_tap_matches_limit_of_general_formula() {
+        let (shift, dt, f_lo_cut, f_hi_cut) = (16, 1e-3, 100.0, 300.0);
+        // General band pass formula evaluated off the integer taps
+        let band_pass = |m: f64| {
+            let sin = |f: f64| (2.0 * PI * f * dt * m).sin();
+            (sin(f_hi_cut) - sin(f_lo_cut)) / (PI * dt * m)
+        };
+        let band_stop = |m: f64| (PI * m).sin() / (PI * dt * m) - band_pass(m);
+        let assert_three_figures = |a: f64, b: f64| assert!(((a - b) / b).abs() < 1e-3);
+
+        let m = 0.001;
+        assert_three_figures(
+            filter_band_pass(shift, shift, dt, f_lo_cut, f_hi_cut),
+            band_pass(m),
+        );
+        assert_three_figures(
+            filter_band_stop(shift, shift, dt, f_lo_cut, f_hi_cut),
+            band_stop(m),
+        );
+    }
+
+    const FUZZED_FILTERS: [Filter; 4] = [
+        Filter::LowPass,
+        Filter::HighPass,
+        Filter::BandPass,
+        Filter::BandStop,
+    ];
+
+    /// Returns the window of the given index, using `parameter` in `[0, 1]` for the parametric ones.
+    fn fuzzed_window(index: usize, parameter: f64) -> Window {
+        match index {
+            0 => Window::Rectangular,
+            1 => Window::Triangular,
+            2 => Window::Welch,
+            3 => Window::Sin,
+            4 => Window::Hann,
+            5 => Window::Hamming,
+            6 => Window::Blackman,
+            7 => Window::Nuttall,
+            8 => Window::BlackmanNuttall,
+            9 => Window::BlackmanHarris,
+            10 => Window::FlatTop,
+            11 => Window::Planck {
+                epsilon: parameter / 2.0,
+            },
+            _ => Window::Dpss {
+                half_bandwidth: 0.001 + parameter * 0.498,
+            },
+        }
+    }
+
+    /// Generates `(len, shift, f_sampling, f_lo_cut, f_hi_cut)` within the design limits, with the
+    /// zero bandwidth edges of the cutoffs drawn about as often as the rest of the range.
+    fn design_parameters() -> impl Strategy<Value = (usize, usize, f64, f64, f64)> {
+        (1..=1024usize, 1.0..=1e9f64)
+            .prop_flat_map(|(len, f_sampling)| {
+                (Just(len), 0..=len, Just(f_sampling), 0.0..=f_sampling / 2.0)
+            })
+            .prop_flat_map(|(len, shift, f_sampling, f_lo_cut)| {
+                (
+                    Just(len),
+                    Just(shift),
+                    Just(f_sampling),
+                    Just(f_lo_cut),
+                    f_lo_cut..=f_sampling / 2.0,
+                    0..4usize,
+                )
+            })
+            .prop_map(|(len, shift, f_sampling, f_lo_cut, f_hi_cut, edge)| {
+                let (f_lo_cut, f_hi_cut) = match edge {
+                    0 => (f_lo_cut, f_lo_cut),
+                    1 => (0.0, f_hi_cut),
+                    2 => (0.0, 0.0),
+                    _ => (f_lo_cut, f_hi_cut),
+                };
+                (len, shift, f_sampling, f_lo_cut, f_hi_cut)
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn random_designs_are_finite_or_rejected(
+            filter in 0..FUZZED_FILTERS.len(),
+            window in 0..13usize,
+            window_parameter in 0.0..=1.0f64,
+            (len, shift, f_sampling, f_lo_cut, f_hi_cut) in design_parameters(),
+        ) {
+            let def = FilterDef {
+                filter: FUZZED_FILTERS[filter].clone(),
+                window: fuzzed_window(window, window_parameter),
+                len,
+                shift,
+                f_sampling,
+                f_lo_cut,
+                f_hi_cut,
+                ..Default::default()
+            };
+            let zero_bandwidth = match def.filter {
+                Filter::LowPass => f_hi_cut == 0.0,
+                Filter::BandPass => f_lo_cut == f_hi_cut,
+                Filter::BandStop => f_lo_cut == 0.0,
+                _ => false,
+            };
+            let w = def.compute_window();
+            prop_assert!(w.iter().all(|w| w.is_finite()));
+
+            if zero_bandwidth {
+                prop_assert_eq!(def.try_compute_filter(), Err(FilterError::ZeroBandwidth));
+            } else {
+                let f = def.try_compute_filter();
+                let f_windowed =
+                    FilterDef::compute_filter_windowed(&def.compute_sinc_coefficients(), &w);
+
+                prop_assert!(f.is_ok_and(|f| f.iter().all(|h| h.is_finite())));
+                prop_assert!(f_windowed.iter().all(|h| h.is_finite()));
+            }
+        }
+    }
+
+    #[test]
+    fn planck_window_is_continuous_at_taper_ends() {
+        let (len, epsilon) = (100_000, 0.1);
+        let taper = (epsilon * len as f64) as usize;
+
+        assert!(window_planck(1, len, epsilon) < 1e-9);
+        assert!((window_planck(taper - 1, len, epsilon) - 1.0).abs() < 1e-9);
+        assert_eq!(window_planck(taper, len, epsilon), 1.0);
+        assert!((1..taper)
+            .all(|n| window_planck(n, len, epsilon) >= window_planck(n - 1, len, epsilon)));
+        for n in [1, taper - 1] {
+            assert_eq!(
+                window_planck(n, len, epsilon),
+                window_planck(len - n, len, epsilon)
+            );
+        }
+    }
+
+    #[test]
+    fn planck_window_approaches_rectangular() {
+        let planck = |epsilon| FilterDef {
+            window: Window::Planck { epsilon },
+            len: 64,
+            shift: 32,
+            ..Default::default()
+        };
+        let rectangular = FilterDef {
+            window: Window::Rectangular,
+            ..planck(0.0)
+        }
+        .compute_window();
+        let deviation = |epsilon| {
+            planck(epsilon)
+                .compute_window()
+                .iter()
+                .zip(&rectangular)
+                .map(|(w, w_rect)| (w - w_rect).abs())
+                .sum::<f64>()
+        };
+
+        assert_eq!(planck(0.0).compute_window(), rectangular);
+        assert!(deviation(0.01) < deviation(0.1));
+        assert!(deviation(0.1) < deviation(0.4));
+        // Only the end samples, which are zero for any taper, are left
+        assert_eq!(deviation(1e-3), 2.0);
+    }
+
+    #[test]
+    fn symmetrize_makes_taps_bitwise_symmetric() {
+        for len in [32, 33] {
+            let mut f = FilterDef {
+                window: Window::Blackman,
+                len,
+                shift: 13,
+                f_sampling: 1000.0,
+                f_hi_cut: 123.4,
+                ..Default::default()
+            }
+            .compute_filter();
+            FilterDef::symmetrize(&mut f);
+
+            for n in 0..len {
+                assert_eq!(f[n].to_bits(), f[len - 1 - n].to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn minimum_phase_equivalent_keeps_magnitude() {
+        let def = FilterDef {
+            window: Window::Hamming,
+            len: 65,
+            shift: 32,
+            f_sampling: 1000.0,
+            f_hi_cut: 200.0,
+            ..Default::default()
+        };
+        let f = def.compute_filter();
+        let f_min_phase = def.minimum_phase_equivalent();
+        let db = |f: &[f64], w| 20.0 * gain_at(f.iter().copied(), w).log10();
+
+        // Away from the stopband nulls, where tiny errors of the truncated response are large in dB
+        for k in 0..DFT_LEN / 2 {
+            let w = 2.0 * PI * k as f64 / DFT_LEN as f64;
+            if db(&f, w) > -40.0 {
+                assert!((db(&f, w) - db(&f_min_phase, w)).abs() < 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn dpss_window_is_leading_slepian_sequence() {
+        let half_bandwidth = 0.1;
+        let v = window_dpss(32, half_bandwidth);
+        let norm = v.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+        // Slepian sequences are the eigenvectors of the concentration into [-W, W]
+        let concentration = |n: usize, m: usize| {
+            let d = n as f64 - m as f64;
+            if n == m {
+                2.0 * half_bandwidth
+            } else {
+                (2.0 * PI * half_bandwidth * d).sin() / (PI * d)
+            }
+        };
+        let a_v: Vec<f64> = (0..v.len())
+            .map(|n| (0..v.len()).map(|m| concentration(n, m) * v[m]).sum())
+            .collect();
+        let lambda = a_v.iter().zip(&v).map(|(a_v, v)| a_v * v).sum::<f64>() / norm.powi(2);
+
+        let residual_norm = a_v
+            .iter()
+            .zip(&v)
+            .map(|(a_v, v)| (a_v - lambda * v).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(residual_norm / norm < 1e-6);
+
+        // A maps sequences orthogonal to the window to sequences orthogonal to it, i.e. the window
+        // is orthogonal to all other Slepian sequences
+        for k in 1..4 {
+            let u: Vec<f64> = (0..v.len()).map(|n| (0.7 * (k * n) as f64).cos()).collect();
+            let u_dot_v = u.iter().zip(&v).map(|(u, v)| u * v).sum::<f64>() / norm.powi(2);
+            let u: Vec<f64> = u.iter().zip(&v).map(|(u, v)| u - u_dot_v * v).collect();
+            let v_a_u: f64 = (0..v.len())
+                .map(|n| {
+                    (0..v.len())
+                        .map(|m| v[n] * concentration(n, m) * u[m])
+                        .sum::<f64>()
+                })
+                .sum();
+            assert!(v_a_u.abs() < 1e-6 * norm.powi(2));
+        }
+        // The leading sequence is almost completely concentrated
+        assert!(lambda > 0.999 && lambda < 1.0);
+    }
+
+    #[test]
+    fn window_mode_sets_window_period() {
+        for window in [Window::Hann, Window::Hamming] {
+            let symmetric = FilterDef {
+                window: window.clone(),
+                len: 16,
+                ..Default::default()
+            };
+            let w = symmetric.compute_window();
+            assert!((w[0] - w[15]).abs() < 1e-12);
+            if window == Window::Hann {
+                assert_eq!(w[0], 0.0);
+                assert!(w[15].abs() < 1e-12);
+            }
+
+            let periodic = FilterDef {
+                window_mode: WindowMode::Periodic,
+                ..symmetric
+            };
+            let w = periodic.compute_window();
+            assert!((w[0] - w[15]).abs() > 1e-3);
+            // The periodic window is symmetric about its period of len samples
+            assert!((w[1] - w[15]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn dpss_window_is_symmetric_with_unit_peak() {
+        for len in [16, 33] {
+            for window_mode in [WindowMode::Symmetric, WindowMode::Periodic] {
+                let def = FilterDef {
+                    window: Window::Dpss {
+                        half_bandwidth: 0.08,
+                    },
+                    window_mode,
+                    len,
+                    ..Default::default()
+                };
+                let w = def.compute_window();
+                assert_eq!(w.len(), len);
+
+                let peak = w.iter().copied().fold(f64::MIN, f64::max);
+                assert!((peak - 1.0).abs() < 1e-12);
+                assert!(w.iter().all(|w| *w > 0.0 && *w <= 1.0));
+
+                // Symmetric about (len - 1) / 2, or about len / 2 for the periodic window
+                let period = window_mode.period(len);
+                for n in 1..len {
+                    let mirrored = period - n;
+                    if mirrored < len {
+                        assert!((w[n] - w[mirrored]).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sinc_known_values() {
+        assert_eq!(sinc(0.0), 1.0);
+        assert!(sinc(1.0).abs() < 1e-15);
+        // 2 / pi ≈ 0.6366
+        assert!((sinc(0.5) - std::f64::consts::FRAC_2_PI).abs() < 1e-15);
+        assert_eq!(sinc(-0.5), sinc(0.5));
+    }
+
+    #[test]
+    fn interpolate_end_points_and_identity() {
+        let a = FilterDef {
+            filter: Filter::Multiband {
+                bands: vec![(0.0, 100.0, true), (100.0, 500.0, false)],
+            },
+            window: Window::Planck { epsilon: 0.1 },
+            len: 33,
+            shift: 16,
+            f_sampling: 1000.0,
+            f_lo_cut: 50.0,
+            f_hi_cut: 200.0,
+            ..Default::default()
+        };
+        let b = FilterDef {
+            filter: Filter::Multiband {
+                bands: vec![(0.0, 150.0, false), (150.0, 500.0, true)],
+            },
+            window: Window::Planck { epsilon: 0.3 },
+            window_mode: WindowMode::Periodic,
+            len: 64,
+            shift: 20,
+            f_sampling: 2000.0,
+            f_lo_cut: 75.0,
+            f_hi_cut: 333.3,
+            force_symmetry: true,
+            normalization: Normalization::UnitEnergy,
+            delay_offset: 3,
+        };
+
+        for t in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert!(interpolate(&a, &a, t) == a);
+            assert!(interpolate(&b, &b, t) == b);
+        }
+        assert!(interpolate(&a, &b, 0.0) == a);
+        assert!(interpolate(&a, &b, 1.0) == b);
+    }
+
+    #[test]
+    fn convolve_is_commutative_and_associative() {
+        let a = [0.3, -1.2, 2.5, 0.7];
+        let b = FilterDef {
+            window: Window::Hann,
+            len: 9,
+            shift: 4,
+            f_sampling: 1000.0,
+            f_hi_cut: 150.0,
+            ..Default::default()
+        }
+        .compute_filter();
+        let c = [1.0, 0.0, -0.5];
+        let assert_close = |x: &[f64], y: &[f64]| {
+            assert_eq!(x.len(), y.len());
+            assert!(x.iter().zip(y).all(|(x, y)| (x - y).abs() < 1e-12));
+        };
+
+        assert_eq!(convolve(&a, &b).len(), a.len() + b.len() - 1);
+        assert_close(&convolve(&a, &b), &convolve(&b, &a));
+        assert_close(
+            &convolve(&convolve(&a, &b), &c),
+            &convolve(&a, &convolve(&b, &c)),
+        );
+    }
+
+    #[test]
+    fn zeros_of_linear_phase_filter_come_in_conjugate_pairs() {
+        let f = FilterDef {
+            window: Window::Hamming,
+            len: 25,
+            shift: 12,
+            f_sampling: 1000.0,
+            f_hi_cut: 200.0,
+            ..Default::default()
+        }
+        .compute_filter();
+        let zeros = FilterDef::compute_zeros(&f);
+        assert_eq!(zeros.len(), 24);
+
+        let has_zero_at = |re: f64, im: f64| {
+            zeros
+                .iter()
+                .any(|(z_re, z_im)| (z_re - re).hypot(z_im - im) < 1e-6)
+        };
+        for (re, im) in &zeros {
+            assert!(has_zero_at(*re, -im));
+            // Linear phase also mirrors the zeros about the unit circle
+            let r2 = re.powi(2) + im.powi(2);
+            assert!(has_zero_at(re / r2, -im / r2));
         }
     }
+
+    #[test]
+    fn point_evaluators_match_computed_vectors() {
+        let windows = [
+            Window::Rectangular,
+            Window::Hann,
+            Window::BlackmanHarris,
+            Window::Planck { epsilon: 0.2 },
+            Window::Dpss {
+                half_bandwidth: 0.1,
+            },
+        ];
+
+        for window in windows {
+            for normalization in [Normalization::UnitPassbandGain, Normalization::UnitEnergy] {
+                let def = FilterDef {
+                    filter: Filter::BandPass,
+                    window: window.clone(),
+                    len: 40,
+                    shift: 18,
+                    f_sampling: 1000.0,
+                    f_lo_cut: 100.0,
+                    f_hi_cut: 300.0,
+                    normalization,
+                    ..Default::default()
+                };
+                let f = def.compute_filter();
+                let f_windowed = FilterDef::compute_filter_windowed(
+                    &def.compute_sinc_coefficients(),
+                    &def.compute_window(),
+                );
+
+                for n in 0..def.len {
+                    assert!((def.coefficient_at(n) - f[n]).abs() < 1e-12);
+                    assert!((def.windowed_coefficient_at(n) - f_windowed[n]).abs() < 1e-12);
+                }
+                assert!(def
+                    .coefficients()
+                    .zip(&f)
+                    .all(|(h, h_f)| (h - h_f).abs() < 1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn ensure_odd_length_keeps_linear_phase() {
+        let mut def = FilterDef {
+            filter: Filter::HighPass,
+            window: Window::Hann,
+            len: 32,
+            shift: 16,
+            f_sampling: 1000.0,
+            f_lo_cut: 300.0,
+            force_symmetry: true,
+            ..Default::default()
+        };
+        let is_symmetric =
+            |f: &[f64]| (0..f.len()).all(|n| (f[n] - f[f.len() - 1 - n]).abs() < 1e-12);
+        assert!(def.is_type_ii());
+        assert!(is_symmetric(&def.compute_filter()));
+
+        // A trailing zero tap makes the length odd but breaks the symmetry
+        let f_padded = FilterDef::even_length_highpass_correction(&def.compute_filter());
+        assert_eq!(f_padded.len(), 33);
+        assert!(!is_symmetric(&f_padded));
+
+        def.ensure_odd_length();
+        let f = def.compute_filter();
+        assert_eq!((def.len, def.shift), (33, 16));
+        assert!(is_symmetric(&f));
+        assert!(!def.is_type_ii());
+        assert!(def.gain_at_nyquist_db().abs() < 1.0);
+
+        def.ensure_odd_length();
+        assert_eq!(def.len, 33);
+    }
+
+    #[test]
+    fn even_length_highpass_is_type_ii_only_if_symmetric() {
+        let mut def = FilterDef {
+            filter: Filter::HighPass,
+            window: Window::Hann,
+            len: 64,
+            shift: 32,
+            f_sampling: 1000.0,
+            f_lo_cut: 100.0,
+            ..Default::default()
+        };
+
+        // The sinc is centered on tap 32, not between taps 31 and 32, so there is no Nyquist null
+        let f = def.compute_filter();
+        assert!((0..f.len()).any(|n| (f[n] - f[f.len() - 1 - n]).abs() > 0.5));
+        assert!(def.gain_at_nyquist_db().abs() < 1.0);
+        assert!(!def.is_type_ii());
+        assert!(!def.warnings().contains(&FilterWarning::EvenLengthHighPass));
+
+        def.force_symmetry = true;
+        assert!(def.is_type_ii());
+        assert!(def.gain_at_nyquist_db() < -60.0);
+        assert!(def.warnings().contains(&FilterWarning::EvenLengthHighPass));
+    }
 }
@@ -1,54 +1,96 @@
-use super::fir::{Filter, FilterDef, Window};
+#[cfg(feature = "audio")]
+use super::audio::AudioMonitor;
 use eframe::egui;
-use egui_plot::{Line, Plot};
-use std::f64::consts::PI;
+use egui_plot::{
+    uniform_grid_spacer, AxisHints, Bar, BarChart, HPlacement, Line, LineStyle, MarkerShape, Plot,
+    PlotBounds, PlotImage, PlotPoint, Points,
+};
+use fir_designer::data::{self, FilterData, FilterMetrics, QuantizationStats};
+use fir_designer::diff;
+use fir_designer::export::{self, Precision};
+use fir_designer::fir::{
+    self, Filter, FilterDef, FilterError, FilterWarning, Normalization, Window, WindowMode,
+    WindowModifier,
+};
+use std::collections::VecDeque;
 use std::fmt;
+use std::path::Path;
 
-const DFT_LEN: usize = 256;
-
-pub struct FilterData {
-    filter: Vec<f64>,
-    window: Vec<f64>,
-    f_windowed: Vec<f64>,
-    filter_imp: Vec<[f64; 2]>,
-    filter_stp: Vec<[f64; 2]>,
-    filter_dft: Vec<[f64; 2]>,
-    window_fun: Vec<[f64; 2]>,
-    window_dft: Vec<[f64; 2]>,
-    f_windowed_imp: Vec<[f64; 2]>,
-    f_windowed_stp: Vec<[f64; 2]>,
-    f_windowed_dft: Vec<[f64; 2]>,
-}
-
-impl From<&FilterDef> for FilterData {
-    fn from(def: &FilterDef) -> Self {
-        let filter = def.compute_filter();
-        let window = def.compute_window();
-        let f_windowed = FilterDef::compute_filter_windowed(&filter, &window);
-        let filter_imp = plot_filter_imp(&filter, def.f_sampling);
-        let filter_stp = plot_filter_stp(&filter, def.f_sampling);
-        let filter_dft = plot_dft(&filter, def.f_sampling);
-        let window_fun = plot_window(&window, def.f_sampling);
-        let window_dft = plot_dft(&window, def.f_sampling);
-        let f_windowed_imp = plot_filter_imp(&f_windowed, def.f_sampling);
-        let f_windowed_stp = plot_filter_stp(&f_windowed, def.f_sampling);
-        let f_windowed_dft = plot_dft(&f_windowed, def.f_sampling);
+const FILTER_DEF_KEY: &str = "filter_def";
+const PLOT_LABELS_KEY: &str = "plot_labels";
+const PIXELS_PER_POINT_KEY: &str = "pixels_per_point";
+const RECENT_DESIGNS_KEY: &str = "recent_designs";
+/// Number of designs kept in the Recent Designs menu.
+const MAX_RECENT_DESIGNS: usize = 10;
+const JSONLINES_PATH: &str = "coefficients.jsonl";
+const BINARY_PATH: &str = "design.fird";
+const TEST_VECTOR_LEN: usize = 4096;
+const FREQUENCY_RESPONSE_PATH: &str = "frequency_response.csv";
+/// Number of frequencies between DC and the Nyquist frequency in the exported frequency response.
+const FREQUENCY_RESPONSE_POINTS: usize = 512;
+const ALIASING_ARTIFACT_LIMIT_DB: f64 = -40.0;
+/// Deviation from 0 dB of the Nyquist gain of a high pass filter that is flagged in the metrics.
+const NYQUIST_GAIN_TOLERANCE_DB: f64 = 1.0;
+/// Passband group delay variation above which the dispersion becomes noticeable.
+const GROUP_DELAY_VARIATION_LIMIT_DB: f64 = -20.0;
+/// Filter lengths spanned by the rows of the design space.
+const DESIGN_SPACE_LENS: std::ops::RangeInclusive<usize> = 8..=128;
+/// Number of high cut frequencies between DC and the Nyquist frequency in the design space.
+const DESIGN_SPACE_COLUMNS: usize = 64;
+/// Stopband attenuation shown in full red in the design space.
+const DESIGN_SPACE_MAX_ATTENUATION_DB: f64 = 100.0;
+/// Number of frequencies the high resolution response takes across the zoomed transition band, and
+/// across the whole band.
+const BAND_EDGE_POINTS: (usize, usize) = (512, 64);
+/// Length of the input signal of the convolution matrix shown in the debug section.
+#[cfg(debug_assertions)]
+const CONVOLUTION_MATRIX_SIGNAL_LEN: usize = 16;
+/// Stopband attenuation and transition width the window recommendation starts with.
+const DEFAULT_RECOMMENDATION_SPEC: (f64, f64) = (60.0, 50.0);
 
-        Self {
-            filter,
-            window,
-            f_windowed,
-            filter_imp,
-            filter_stp,
-            filter_dft,
-            window_fun,
-            window_dft,
-            f_windowed_imp,
-            f_windowed_stp,
-            f_windowed_dft,
-        }
-    }
-}
+/// Plots in the order of the title and axis label arrays of [`App`].
+const PLOT_NAMES: [&str; 4] = [
+    "Filter Time Domain",
+    "Filter Frequency Domain",
+    "Window Time Domain",
+    "Window Frequency Domain",
+];
+const DEFAULT_PLOT_TITLES: [&str; 4] = [
+    "Filter Response (Time Domain)",
+    "Filter Response (Frequency Domain)",
+    "Window Function (Time Domain)",
+    "Window Function (Frequency Domain)",
+];
+const DEFAULT_X_LABELS: [&str; 4] = ["Time (s)", "Frequency (Hz)", "Time (s)", "Frequency (Hz)"];
+const SAMPLING_FREQUENCY_TOOLTIP: &str =
+    "Sample rate of the signal the filter is applied to. All frequencies are limited to half of it.";
+const LENGTH_TOOLTIP: &str = "Number of filter taps. Longer filters have steeper transitions, an \
+    odd length is recommended for linear phase.";
+const SHIFT_TOOLTIP: &str = "Number of samples of group delay, the tap the sinc is centered on. \
+    Typically set to (Length-1)/2 for linear phase response.";
+const FILTER_TYPE_TOOLTIP: &str = "Ideal frequency response the filter approximates.";
+const EXPRESSION_TOOLTIP: &str = "Raw coefficient at tap n before windowing and normalization.";
+const LO_CUT_TOOLTIP: &str = "Lower band edge in Hz. Used by high pass, band pass and band stop \
+    filters, it must be below the high cut.";
+const HI_CUT_TOOLTIP: &str = "Upper band edge in Hz. Used by low pass, band pass and band stop \
+    filters, it must be below half the sampling frequency.";
+const WINDOW_TOOLTIP: &str = "Taper applied to the truncated sinc. Hamming is a good default, \
+    Blackman or Blackman-Harris trade a wider transition for a deeper stopband.";
+const EPSILON_TOOLTIP: &str =
+    "Fraction of the window tapered on each side. Values around 0.1 are typical.";
+const HALF_BANDWIDTH_TOOLTIP: &str = "Normalized half bandwidth of the main lobe the energy is \
+    concentrated in. Values around 0.05 are typical.";
+const WINDOW_MODIFIER_TOOLTIP: &str = "Derive a new window from the selected one. Squaring or \
+    convolving widens the main lobe and lowers the side lobes, the square root does the opposite.";
+const CONVOLVED_WINDOW_TOOLTIP: &str = "Second window convolved with the selected one.";
+const WINDOW_MODE_TOOLTIP: &str = "Symmetric windows are used for filter design, periodic windows \
+    for spectral analysis.";
+const FORCE_SYMMETRY_TOOLTIP: &str = "Average mirrored taps to make the coefficients exactly \
+    symmetric. Recommended for linear phase designs.";
+const NORMALIZATION_TOOLTIP: &str = "Scale of the coefficients. Unit passband gain is the usual \
+    choice, unit energy suits matched filters. All gains in the metrics follow the scale.";
+const NORMALIZED_FREQUENCY_LABEL: &str = "Normalized Frequency (× f_s/2)";
+const SAMPLE_INDEX_LABEL: &str = "Sample Index (n)";
 
 #[derive(Default, PartialEq)]
 enum PlotType {
@@ -66,262 +108,2796 @@ impl fmt::Display for PlotType {
     }
 }
 
+/// Rendering of the impulse response.
+#[derive(Default, PartialEq)]
+enum TimeResponseStyle {
+    /// Samples joined by lines over time.
+    #[default]
+    Connected,
+    /// Lines from zero to markers at each sample index.
+    Stem,
+    /// Bars from zero at each sample index.
+    Bar,
+}
+
+impl TimeResponseStyle {
+    /// Returns true if the samples are drawn individually over the sample index.
+    fn is_discrete(&self) -> bool {
+        !matches!(self, Self::Connected)
+    }
+}
+
+impl fmt::Display for TimeResponseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connected => write!(f, "Connected"),
+            Self::Stem => write!(f, "Stem"),
+            Self::Bar => write!(f, "Bar"),
+        }
+    }
+}
+
+#[derive(Default, PartialEq)]
+enum AspectRatio {
+    #[default]
+    Widescreen,
+    Standard,
+    Photo,
+    Square,
+}
+
+impl AspectRatio {
+    fn value(&self) -> f32 {
+        match self {
+            Self::Widescreen => 16.0 / 9.0,
+            Self::Standard => 4.0 / 3.0,
+            Self::Photo => 3.0 / 2.0,
+            Self::Square => 1.0,
+        }
+    }
+}
+
+impl fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Widescreen => write!(f, "16:9"),
+            Self::Standard => write!(f, "4:3"),
+            Self::Photo => write!(f, "3:2"),
+            Self::Square => write!(f, "1:1"),
+        }
+    }
+}
+
+/// Curves colored by [`ColorTheme`].
+#[derive(Clone, Copy, PartialEq)]
+enum Curve {
+    Filter,
+    Windowed,
+    Window,
+}
+
+#[derive(Default, PartialEq)]
+enum ColorTheme {
+    #[default]
+    Default,
+    Colorblind,
+    Grayscale,
+    Publication,
+}
+
+impl ColorTheme {
+    /// Returns the color of a curve, `None` keeps the automatic plot colors.
+    fn color(&self, curve: Curve) -> Option<egui::Color32> {
+        match (self, curve) {
+            (Self::Default, _) => None,
+            // Okabe-Ito palette
+            (Self::Colorblind, Curve::Filter) => Some(egui::Color32::from_rgb(230, 159, 0)),
+            (Self::Colorblind, Curve::Windowed) => Some(egui::Color32::from_rgb(0, 114, 178)),
+            (Self::Colorblind, Curve::Window) => Some(egui::Color32::from_rgb(0, 158, 115)),
+            (Self::Grayscale, Curve::Filter) => Some(egui::Color32::from_gray(160)),
+            (Self::Grayscale, Curve::Windowed) => Some(egui::Color32::from_gray(0)),
+            (Self::Grayscale, Curve::Window) => Some(egui::Color32::from_gray(80)),
+            (Self::Publication, Curve::Filter) => Some(egui::Color32::GRAY),
+            (Self::Publication, Curve::Windowed | Curve::Window) => Some(egui::Color32::BLACK),
+        }
+    }
+
+    fn line_style(&self, curve: Curve) -> LineStyle {
+        match (self, curve) {
+            (Self::Publication, Curve::Window) => LineStyle::dashed_loose(),
+            _ => LineStyle::Solid,
+        }
+    }
+
+    /// Returns true if the theme is meant for printing and uses the light visuals.
+    fn is_light(&self) -> bool {
+        matches!(self, Self::Grayscale | Self::Publication)
+    }
+
+    fn style(&self, line: Line, curve: Curve) -> Line {
+        let line = line.style(self.line_style(curve));
+
+        match self.color(curve) {
+            Some(color) => line.color(color),
+            None => line,
+        }
+    }
+}
+
+impl fmt::Display for ColorTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "Default"),
+            Self::Colorblind => write!(f, "Colorblind"),
+            Self::Grayscale => write!(f, "Grayscale"),
+            Self::Publication => write!(f, "Publication"),
+        }
+    }
+}
+
 pub struct App {
     filter_def: FilterDef,
+    /// The design as last saved with "Export JSON" or "Save Binary", to detect unsaved changes.
+    saved_filter_def: FilterDef,
+    confirm_reset: bool,
+    /// Most recently saved or opened designs with their description, newest first.
+    recent_designs: VecDeque<(String, FilterDef)>,
     filter_data: FilterData,
+    /// Why the design cannot be computed, the plot data is then empty.
+    filter_error: Option<FilterError>,
     plot_type: PlotType,
+    time_response_style: TimeResponseStyle,
     show_window: bool,
+    normalize_amplitude: bool,
+    show_autocorrelation: bool,
+    show_phase: bool,
+    show_pole_zero: bool,
+    show_chirp_test: bool,
+    /// Save a screenshot of the window whenever the design changes, see
+    /// [`App::with_screenshot_on_parameter_change`].
+    screenshot_on_parameter_change: bool,
+    /// A screenshot of the updated plots is requested in the next frame.
+    screenshot_pending: bool,
+    show_design_space: bool,
+    /// Stopband attenuation for each length in [`DESIGN_SPACE_LENS`], a row is only computed once
+    /// the length is selected.
+    design_space: Vec<Option<Vec<f64>>>,
+    /// Design the rows were computed for, with the length, shift and high cut spanning the grid
+    /// zeroed.
+    design_space_def: Option<FilterDef>,
+    design_space_texture: Option<egui::TextureHandle>,
+    show_recommendations: bool,
+    /// Stopband attenuation in dB and transition width in Hz windows are recommended for.
+    recommendation_spec: (f64, f64),
+    /// Recommended windows with their length and the metrics of the current design using them.
+    recommendations: Vec<(Window, usize, FilterMetrics)>,
+    /// Chirp input and filtered output, only computed while the chirp test is shown.
+    chirp_test: Option<(Vec<f64>, Vec<f64>)>,
+    /// Zeros of the windowed filter, only computed while the pole-zero plot is shown.
+    zeros: Option<Vec<(f64, f64)>>,
+    show_psd: bool,
+    freq_zoom: Option<PlotBounds>,
+    reset_freq_zoom: bool,
+    high_res_band_edge: bool,
+    /// Windowed response sampled densely around the transition band, only computed while the high
+    /// resolution band edge is shown.
+    band_edge_response: Option<Vec<[f64; 2]>>,
+    show_min_phase: bool,
+    f_min_phase: Option<Vec<f64>>,
+    lock_aspect_ratio: bool,
+    aspect_ratio: AspectRatio,
+    lock_y_axis: bool,
+    time_y_range: [f64; 2],
+    reset_time_zoom: bool,
+    reset_window_zoom: bool,
+    /// Parameter fields of the last frame in the order Tab moves the focus through them.
+    parameter_ids: Vec<egui::Id>,
+    filter_string_input: String,
+    filter_string_error: Option<String>,
+    plot_titles: [String; 4],
+    plot_x_labels: [String; 4],
+    plot_y_labels: [String; 4],
+    reference_def: Option<FilterDef>,
+    morph_t: f64,
+    f_morph_dft: Option<Vec<[f64; 2]>>,
+    show_weighted: bool,
+    passband_weight: f64,
+    stopband_weight: f64,
+    /// Response of the weighted least squares design, only computed while it is shown.
+    f_weighted_dft: Option<Vec<[f64; 2]>>,
+    normalize_nyquist: bool,
+    window_correction: bool,
+    /// Overlays the spectrum after decimation by this factor when above 1.
+    decimation_factor: usize,
+    color_theme: ColorTheme,
+    target_path: String,
+    target_response: Option<Vec<[f64; 2]>>,
+    f_matched_dft: Option<Vec<[f64; 2]>>,
+    target_error: Option<String>,
+    cascade_path: String,
+    cascade_def: Option<FilterDef>,
+    /// The current design in series with the design loaded from `cascade_path`.
+    cascade_data: Option<FilterData>,
+    cascade_error: Option<String>,
+    zero_phase_output: bool,
+    rust_const_precision: Precision,
+    /// Overrides the scale factor of the system when set.
+    pixels_per_point: Option<f32>,
+    native_pixels_per_point: Option<f32>,
+    jsonlines_status: Option<String>,
+    binary_status: Option<String>,
+    signal_path: String,
+    signal: Option<Vec<f64>>,
+    signal_sample_rate: Option<u32>,
+    signal_status: Option<String>,
+    quantization_bits: u8,
+    quantization_trials: usize,
+    quantization_stats: Option<QuantizationStats>,
+    #[cfg(feature = "audio")]
+    audio_monitor: Option<AudioMonitor>,
+    #[cfg(feature = "audio")]
+    audio_error: Option<String>,
 }
 
 impl App {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        let mut filter_def = FilterDef::default();
-        filter_def.f_sampling = 1000.0;
-        filter_def.len = 64;
-        filter_def.shift = 32;
-        filter_def.f_lo_cut = 100.0;
-        filter_def.f_hi_cut = 300.0;
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let filter_def = cc
+            .storage
+            .and_then(|storage| App::load_json(storage, FILTER_DEF_KEY))
+            .unwrap_or_else(App::default_filter_def);
+        let [plot_titles, plot_x_labels, plot_y_labels] = cc
+            .storage
+            .and_then(|storage| App::load_json(storage, PLOT_LABELS_KEY))
+            .unwrap_or_else(|| {
+                [
+                    DEFAULT_PLOT_TITLES.map(String::from),
+                    DEFAULT_X_LABELS.map(String::from),
+                    Default::default(),
+                ]
+            });
 
-        let filter_data = FilterData::from(&filter_def);
+        let pixels_per_point: Option<f32> = cc
+            .storage
+            .and_then(|storage| App::load_json(storage, PIXELS_PER_POINT_KEY));
+        if let Some(pixels_per_point) = pixels_per_point {
+            cc.egui_ctx.set_pixels_per_point(pixels_per_point);
+        }
+
+        let recent_designs = cc
+            .storage
+            .and_then(|storage| App::load_json(storage, RECENT_DESIGNS_KEY))
+            .unwrap_or_default();
+
+        let filter_data = FilterData::try_from(&filter_def);
 
         Self {
+            saved_filter_def: filter_def.clone(),
+            confirm_reset: false,
+            recent_designs,
             filter_def,
-            filter_data,
+            filter_error: filter_data.as_ref().err().cloned(),
+            filter_data: filter_data.unwrap_or_default(),
             plot_type: PlotType::default(),
+            time_response_style: TimeResponseStyle::default(),
             show_window: true,
+            normalize_amplitude: false,
+            show_autocorrelation: false,
+            show_phase: false,
+            show_pole_zero: false,
+            show_chirp_test: false,
+            screenshot_on_parameter_change: false,
+            screenshot_pending: false,
+            show_design_space: false,
+            design_space: Vec::new(),
+            design_space_def: None,
+            design_space_texture: None,
+            show_recommendations: false,
+            recommendation_spec: DEFAULT_RECOMMENDATION_SPEC,
+            recommendations: Vec::new(),
+            chirp_test: None,
+            zeros: None,
+            show_psd: false,
+            freq_zoom: None,
+            reset_freq_zoom: false,
+            high_res_band_edge: false,
+            band_edge_response: None,
+            show_min_phase: false,
+            f_min_phase: None,
+            lock_aspect_ratio: false,
+            aspect_ratio: AspectRatio::default(),
+            lock_y_axis: false,
+            time_y_range: [0.0, 1.0],
+            reset_time_zoom: false,
+            reset_window_zoom: false,
+            parameter_ids: Vec::new(),
+            filter_string_input: String::new(),
+            filter_string_error: None,
+            plot_titles,
+            plot_x_labels,
+            plot_y_labels,
+            reference_def: None,
+            morph_t: 0.5,
+            f_morph_dft: None,
+            show_weighted: false,
+            passband_weight: 1.0,
+            stopband_weight: 1.0,
+            f_weighted_dft: None,
+            normalize_nyquist: false,
+            window_correction: false,
+            decimation_factor: 1,
+            color_theme: ColorTheme::default(),
+            target_path: "target_response.csv".to_string(),
+            target_response: None,
+            f_matched_dft: None,
+            target_error: None,
+            cascade_path: "design.json".to_string(),
+            cascade_def: None,
+            cascade_data: None,
+            cascade_error: None,
+            zero_phase_output: false,
+            rust_const_precision: Precision::default(),
+            pixels_per_point,
+            native_pixels_per_point: cc.integration_info.native_pixels_per_point,
+            jsonlines_status: None,
+            binary_status: None,
+            signal_path: "signal.csv".to_string(),
+            signal: None,
+            signal_sample_rate: None,
+            signal_status: None,
+            quantization_bits: 16,
+            quantization_trials: 1000,
+            quantization_stats: None,
+            #[cfg(feature = "audio")]
+            audio_monitor: None,
+            #[cfg(feature = "audio")]
+            audio_error: None,
+        }
+    }
+
+    fn default_filter_def() -> FilterDef {
+        FilterDef {
+            f_sampling: 1000.0,
+            len: 64,
+            shift: 32,
+            f_lo_cut: 100.0,
+            f_hi_cut: 300.0,
+            ..Default::default()
+        }
+    }
+
+    fn load_json<T: serde::de::DeserializeOwned>(
+        storage: &dyn eframe::Storage,
+        key: &str,
+    ) -> Option<T> {
+        let json = storage.get_string(key)?;
+
+        match serde_json::from_str(&json) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                eprintln!("warning: could not restore the previous {}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    fn save_json<T: serde::Serialize>(storage: &mut dyn eframe::Storage, key: &str, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(json) => storage.set_string(key, json),
+            Err(err) => eprintln!("warning: could not save the {}: {}", key, err),
+        }
+    }
+
+    /// Saves a PNG of the window to `screenshot_<timestamp>_<len>_<filter>_<window>.png` in the
+    /// working directory whenever the design changes, with the timestamp in milliseconds since the
+    /// Unix epoch. The live window is captured, so the window must be visible.
+    pub fn with_screenshot_on_parameter_change(mut self, enabled: bool) -> Self {
+        self.screenshot_on_parameter_change = enabled;
+        self
+    }
+
+    fn save_screenshot(&self, screenshot: &egui::ColorImage) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis());
+        let name = |value: &dyn fmt::Display| -> String {
+            value
+                .to_string()
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .collect()
+        };
+        let path = format!(
+            "screenshot_{}_{}_{}_{}.png",
+            timestamp,
+            self.filter_def.len,
+            name(&self.filter_def.filter),
+            name(&self.filter_def.window)
+        );
+
+        let result = image::save_buffer(
+            &path,
+            screenshot.as_raw(),
+            screenshot.width() as u32,
+            screenshot.height() as u32,
+            image::ColorType::Rgba8,
+        );
+        match result {
+            Ok(()) => println!("wrote {}", path),
+            Err(err) => eprintln!("error: could not write {}: {}", path, err),
+        }
+    }
+
+    /// Records the current design as saved and adds it to the recent designs.
+    fn mark_saved(&mut self) {
+        self.saved_filter_def = self.filter_def.clone();
+        self.add_recent_design(&self.filter_def.clone());
+    }
+
+    /// Moves the design to the top of the recent designs, dropping the oldest entry if the list is
+    /// full.
+    fn add_recent_design(&mut self, filter_def: &FilterDef) {
+        self.recent_designs.retain(|(_, def)| def != filter_def);
+        let description = format!("{}, {}", filter_def.to_short_filter_string(), today());
+        self.recent_designs
+            .push_front((description, filter_def.clone()));
+        self.recent_designs.truncate(MAX_RECENT_DESIGNS);
+    }
+
+    fn draw_window_combo_box(ui: &mut egui::Ui, window: &mut Window, id: &str) {
+        egui::ComboBox::from_id_source(id)
+            .selected_text(format!("{}", window))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(window, Window::Rectangular, "Rectangular");
+                ui.selectable_value(window, Window::Triangular, "Triangular");
+                ui.selectable_value(window, Window::Welch, "Welch");
+                ui.selectable_value(window, Window::Sin, "Sin");
+                ui.selectable_value(window, Window::Hann, "Hann");
+                ui.selectable_value(window, Window::Hamming, "Hamming");
+                ui.selectable_value(window, Window::Blackman, "Blackman");
+                ui.selectable_value(window, Window::Nuttall, "Nutall");
+                ui.selectable_value(window, Window::BlackmanNuttall, "Blackman Nuttull");
+                ui.selectable_value(window, Window::BlackmanHarris, "Blackman Harris");
+                ui.selectable_value(window, Window::FlatTop, "Flat Top");
+                let is_planck = matches!(window, Window::Planck { .. });
+                if ui.selectable_label(is_planck, "Planck").clicked() && !is_planck {
+                    *window = Window::Planck { epsilon: 0.1 };
+                }
+                let is_dpss = matches!(window, Window::Dpss { .. });
+                if ui.selectable_label(is_dpss, "DPSS").clicked() && !is_dpss {
+                    *window = Window::Dpss {
+                        half_bandwidth: 0.05,
+                    };
+                }
+            });
+    }
+
+    /// Wraps the window in the selected modifier, or unwraps it when none is selected.
+    fn draw_window_modifier_combo_box(ui: &mut egui::Ui, window: &mut Window) {
+        let current = match window {
+            Window::Modified { modifier, .. } => Some(modifier.clone()),
+            _ => None,
+        };
+        let selected_text = match &current {
+            Some(modifier) => format!("{}", modifier),
+            None => "None".to_string(),
+        };
+
+        let mut selected = current.clone();
+        egui::ComboBox::from_id_source("window_modifier")
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                let is_convolved = matches!(current, Some(WindowModifier::Convolved(_)));
+                ui.selectable_value(&mut selected, None, "None");
+                for modifier in [
+                    WindowModifier::Squared,
+                    WindowModifier::Sqrt,
+                    WindowModifier::Cube,
+                ] {
+                    let text = format!("{}", modifier);
+                    ui.selectable_value(&mut selected, Some(modifier), text);
+                }
+                if ui.selectable_label(is_convolved, "Convolved").clicked() && !is_convolved {
+                    selected = Some(WindowModifier::Convolved(Box::new(Window::Rectangular)));
+                }
+            });
+
+        if selected != current {
+            let base = match &*window {
+                Window::Modified { base, .. } => base.as_ref().clone(),
+                window => window.clone(),
+            };
+            *window = match selected {
+                Some(modifier) => Window::Modified {
+                    base: Box::new(base),
+                    modifier,
+                },
+                None => base,
+            };
+        }
+    }
+
+    /// Returns pass, stop, pass bands split at the current cut frequencies.
+    fn default_bands(&self) -> Vec<(f64, f64, bool)> {
+        let f_nyquist = self.filter_def.f_sampling / 2.0;
+        let f_lo = self.filter_def.f_lo_cut.min(f_nyquist);
+        let f_hi = self.filter_def.f_hi_cut.clamp(f_lo, f_nyquist);
+
+        vec![
+            (0.0, f_lo, true),
+            (f_lo, f_hi, false),
+            (f_hi, f_nyquist, true),
+        ]
+    }
+
+    fn draw_band_rows(ui: &mut egui::Ui, bands: &mut Vec<(f64, f64, bool)>, f_sampling: f64) {
+        ui.label("Bands (Hz):");
+        ui.vertical(|ui| {
+            let mut remove = None;
+            for (i, (f_lo, f_hi, is_passband)) in bands.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(f_lo)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f64::NAN)
+                            .max_decimals(0),
+                    );
+                    ui.label("to");
+                    ui.add(
+                        egui::DragValue::new(f_hi)
+                            .speed(0.1)
+                            .clamp_range(0.0..=f64::NAN)
+                            .max_decimals(0),
+                    );
+                    ui.checkbox(is_passband, "Pass");
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = remove {
+                bands.remove(i);
+            }
+
+            if ui.button("Add Band").clicked() {
+                let (f_lo, is_passband) = bands
+                    .last()
+                    .map_or((0.0, true), |(_, f_hi, is_passband)| (*f_hi, !is_passband));
+                bands.push((f_lo, f_sampling / 2.0, is_passband));
+            }
+        });
+    }
+
+    fn draw_param_label(
+        ui: &mut egui::Ui,
+        text: &str,
+        tooltip: &str,
+        warning: Option<&FilterWarning>,
+    ) {
+        match warning {
+            Some(warning) => {
+                ui.label(egui::RichText::new(format!("⚠ {}", text)).color(egui::Color32::YELLOW))
+                    .on_hover_text(format!("{}\n\n⚠ {}", tooltip, warning));
+            }
+            None => {
+                ui.label(text).on_hover_text(tooltip);
+            }
+        }
+    }
+
+    fn draw_autocorrelation_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Autocorrelation")
+            .open(&mut self.show_autocorrelation)
+            .show(ctx, |ui| {
+                if !self.filter_data.autocorrelation_symmetric {
+                    ui.label(
+                        egui::RichText::new("⚠ Autocorrelation is not symmetric")
+                            .color(egui::Color32::YELLOW),
+                    );
+                }
+
+                let plot_autocorrelation = Plot::new("autocorrelation")
+                    .width(400.0)
+                    .height(250.0)
+                    .allow_scroll(false)
+                    .x_axis_label("Lag (Samples)")
+                    .y_axis_width(3);
+
+                plot_autocorrelation.show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(self.filter_data.autocorrelation.clone()));
+                });
+            });
+    }
+
+    fn draw_pole_zero_window(&mut self, ctx: &egui::Context) {
+        let Some(zeros) = &self.zeros else {
+            return;
+        };
+
+        egui::Window::new("Pole-Zero Plot")
+            .open(&mut self.show_pole_zero)
+            .show(ctx, |ui| {
+                let unit_circle: Vec<[f64; 2]> = (0..=256)
+                    .map(|k| {
+                        let theta = 2.0 * std::f64::consts::PI * k as f64 / 256.0;
+                        [theta.cos(), theta.sin()]
+                    })
+                    .collect();
+                let zeros: Vec<[f64; 2]> = zeros.iter().map(|(re, im)| [*re, *im]).collect();
+                let pole_order = self.filter_data.f_windowed.len().saturating_sub(1);
+
+                let plot_pole_zero = Plot::new("pole_zero")
+                    .width(300.0)
+                    .height(300.0)
+                    .data_aspect(1.0)
+                    .allow_scroll(false)
+                    .x_axis_label("Real")
+                    .y_axis_label("Imaginary")
+                    .legend(egui_plot::Legend::default().text_style(egui::TextStyle::Small));
+
+                plot_pole_zero.show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(unit_circle)
+                            .name("Unit Circle")
+                            .style(LineStyle::dashed_loose()),
+                    );
+                    plot_ui.points(
+                        Points::new(zeros)
+                            .name("Zeros")
+                            .shape(MarkerShape::Circle)
+                            .filled(false)
+                            .radius(4.0),
+                    );
+                    if pole_order > 0 {
+                        plot_ui.points(
+                            Points::new(vec![[0.0, 0.0]])
+                                .name(format!("Pole (Order {})", pole_order))
+                                .shape(MarkerShape::Cross)
+                                .radius(5.0),
+                        );
+                    }
+                });
+            });
+    }
+
+    fn draw_chirp_test_window(&mut self, ctx: &egui::Context) {
+        let Some((input, output)) = &self.chirp_test else {
+            return;
+        };
+
+        egui::Window::new("Chirp Test")
+            .open(&mut self.show_chirp_test)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Linear chirp from 0 Hz to {} Hz over {} samples",
+                    self.filter_def.f_sampling / 2.0,
+                    input.len()
+                ));
+
+                let dt = 1.0 / self.filter_def.f_sampling;
+                let to_points = |signal: &[f64]| -> Vec<[f64; 2]> {
+                    signal
+                        .iter()
+                        .enumerate()
+                        .map(|(n, x)| [n as f64 * dt, *x])
+                        .collect()
+                };
+
+                let plot_chirp = Plot::new("chirp_test")
+                    .width(500.0)
+                    .height(250.0)
+                    .allow_scroll(false)
+                    .x_axis_label("Time (s)")
+                    .y_axis_label("Amplitude")
+                    .legend(egui_plot::Legend::default().text_style(egui::TextStyle::Small));
+
+                plot_chirp.show(ui, |plot_ui| {
+                    plot_ui.line(
+                        Line::new(to_points(input))
+                            .name("Chirp Input")
+                            .color(egui::Color32::GRAY),
+                    );
+                    plot_ui.line(
+                        Line::new(to_points(output))
+                            .name("Filtered Output")
+                            .color(egui::Color32::LIGHT_BLUE),
+                    );
+                });
+            });
+    }
+
+    fn design_space_cutoffs(&self) -> Vec<f64> {
+        let f_nyquist = self.filter_def.f_sampling / 2.0;
+
+        (0..DESIGN_SPACE_COLUMNS)
+            .map(|c| (c as f64 + 0.5) / DESIGN_SPACE_COLUMNS as f64 * f_nyquist)
+            .collect()
+    }
+
+    /// Computes the design space row of the current length if missing and renders the grid into a
+    /// texture, discarding all rows when a parameter other than the length or high cut changed.
+    fn update_design_space(&mut self, ctx: &egui::Context) {
+        let n_rows = DESIGN_SPACE_LENS.end() - DESIGN_SPACE_LENS.start() + 1;
+        let design_space_def = FilterDef {
+            len: 0,
+            shift: 0,
+            f_hi_cut: 0.0,
+            ..self.filter_def.clone()
+        };
+        if self.design_space_def.as_ref() != Some(&design_space_def) {
+            self.design_space = vec![None; n_rows];
+            self.design_space_def = Some(design_space_def);
+            self.design_space_texture = None;
+        }
+
+        let len = self.filter_def.len;
+        if DESIGN_SPACE_LENS.contains(&len) {
+            let row = len - DESIGN_SPACE_LENS.start();
+            if self.design_space[row].is_none() {
+                let cutoffs = self.design_space_cutoffs();
+                self.design_space[row] = Some(data::compute_design_space_row(
+                    &self.filter_def,
+                    len,
+                    &cutoffs,
+                ));
+                self.design_space_texture = None;
+            }
+        }
+
+        if self.design_space_texture.is_none() {
+            // The longest filter is the top row of the image
+            let mut image =
+                egui::ColorImage::new([DESIGN_SPACE_COLUMNS, n_rows], egui::Color32::TRANSPARENT);
+            for (row, attenuations) in self.design_space.iter().enumerate() {
+                let Some(attenuations) = attenuations else {
+                    continue;
+                };
+                for (column, db) in attenuations.iter().enumerate() {
+                    image[(column, n_rows - 1 - row)] = attenuation_color(*db);
+                }
+            }
+            self.design_space_texture =
+                Some(ctx.load_texture("design_space", image, egui::TextureOptions::NEAREST));
+        }
+    }
+
+    fn draw_design_space_window(&mut self, ctx: &egui::Context) {
+        let Some(texture) = &self.design_space_texture else {
+            return;
+        };
+
+        egui::Window::new("Design Space")
+            .open(&mut self.show_design_space)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Stopband attenuation from 0 dB (blue) to {} dB (red), rows fill in as the \
+                     length is changed",
+                    DESIGN_SPACE_MAX_ATTENUATION_DB
+                ));
+
+                let f_nyquist = self.filter_def.f_sampling / 2.0;
+                let len_min = *DESIGN_SPACE_LENS.start() as f64;
+                let len_max = *DESIGN_SPACE_LENS.end() as f64;
+
+                let plot_design_space = Plot::new("design_space")
+                    .width(400.0)
+                    .height(300.0)
+                    .allow_scroll(false)
+                    .x_axis_label("High Cut (Hz)")
+                    .y_axis_label("Length (samples)");
+
+                plot_design_space.show(ui, |plot_ui| {
+                    plot_ui.image(PlotImage::new(
+                        texture,
+                        PlotPoint::new(f_nyquist / 2.0, (len_min + len_max) / 2.0),
+                        [f_nyquist as f32, (len_max - len_min + 1.0) as f32],
+                    ));
+                    plot_ui.points(
+                        Points::new(vec![[self.filter_def.f_hi_cut, self.filter_def.len as f64]])
+                            .name("Current Design")
+                            .shape(MarkerShape::Cross)
+                            .radius(5.0),
+                    );
+                });
+            });
+    }
+
+    /// Ranks the windows meeting the recommendation spec, evaluating the current design with each.
+    fn compute_recommendations(&mut self) {
+        let (attenuation_db, transition_width_hz) = self.recommendation_spec;
+        self.recommendations = data::recommend_window(
+            attenuation_db,
+            transition_width_hz,
+            self.filter_def.f_sampling,
+        )
+        .into_iter()
+        .map(|(window, len)| {
+            let metrics = FilterDef {
+                window: window.clone(),
+                len,
+                shift: len / 2,
+                ..self.filter_def.clone()
+            }
+            .compute_all_metrics();
+            (window, len, metrics)
+        })
+        .collect();
+    }
+
+    fn draw_recommendation_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_recommendations;
+        let mut adopted = None;
+
+        egui::Window::new("Window Recommendation")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Stopband Attenuation (dB):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.recommendation_spec.0)
+                            .speed(0.1)
+                            .clamp_range(1.0..=200.0),
+                    );
+                    ui.label("Transition Width (Hz):");
+                    ui.add(
+                        egui::DragValue::new(&mut self.recommendation_spec.1)
+                            .speed(0.1)
+                            .clamp_range(0.1..=self.filter_def.f_sampling / 2.0),
+                    );
+                    if ui.button("Recommend").clicked() {
+                        self.compute_recommendations();
+                    }
+                });
+
+                if self.recommendations.is_empty() {
+                    ui.label("No window meets the specification, click Recommend to search");
+                    return;
+                }
+                ui.label("Click a window to adopt it with its length");
+
+                egui::Grid::new("recommendations")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Window");
+                        ui.strong("Required Length");
+                        ui.strong("Passband Ripple");
+                        ui.strong("Stopband Attenuation");
+                        ui.end_row();
+
+                        for (window, len, metrics) in &self.recommendations {
+                            let selected =
+                                self.filter_def.window == *window && self.filter_def.len == *len;
+                            if ui
+                                .selectable_label(selected, format!("{}", window))
+                                .clicked()
+                            {
+                                adopted = Some((window.clone(), *len));
+                            }
+                            ui.label(format!("{}", len));
+                            ui.label(format!("{:.3} dB", metrics.passband_ripple_db));
+                            ui.label(format!("{:.1} dB", metrics.stopband_attenuation_db));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some((window, len)) = adopted {
+            self.filter_def.window = window;
+            self.filter_def.len = len;
+            self.filter_def.shift = len / 2;
         }
+        self.show_recommendations = open;
+    }
+
+    fn draw_phase_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Phase Response")
+            .open(&mut self.show_phase)
+            .show(ctx, |ui| {
+                // The phase error only matters where the filter passes the signal
+                let phase_error = self
+                    .filter_data
+                    .phase_response
+                    .iter()
+                    .zip(&self.filter_data.ideal_phase)
+                    .filter(|([f, _], _)| self.filter_def.is_passband(*f))
+                    .fold(0.0, |max: f64, ([_, phase], [_, ideal])| {
+                        max.max((phase - ideal).abs())
+                    });
+                ui.label(format!("Max. Passband Phase Error: {:.3} rad", phase_error));
+
+                let plot_phase = Plot::new("phase")
+                    .width(400.0)
+                    .height(250.0)
+                    .allow_scroll(false)
+                    .x_axis_label("Frequency (Hz)")
+                    .y_axis_label("Phase (rad)")
+                    .y_axis_width(3)
+                    .legend(egui_plot::Legend::default().text_style(egui::TextStyle::Small));
+
+                plot_phase.show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(self.filter_data.phase_response.clone()).name("Filter"));
+                    plot_ui.line(
+                        Line::new(self.filter_data.ideal_phase.clone())
+                            .name("Ideal Linear Phase")
+                            .style(LineStyle::dashed_loose()),
+                    );
+                });
+            });
+    }
+
+    fn draw_side_panel(&mut self, ctx: &egui::Context) {
+        egui::SidePanel::right("right_panel").show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label("Filter Parameters");
+            ui.separator();
+            self.draw_filter_params_section(ui);
+
+            ui.add_space(40.0);
+            ui.label("Plot");
+            ui.separator();
+            self.draw_plot_params_section(ui);
+
+            ui.add_space(40.0);
+            ui.label("Metrics");
+            ui.separator();
+            self.draw_metrics_section(ui);
+
+            ui.add_space(40.0);
+            ui.label("File");
+            ui.separator();
+            self.draw_file_section(ui);
+
+            #[cfg(feature = "audio")]
+            {
+                ui.add_space(40.0);
+                ui.label("Audio");
+                ui.separator();
+                self.draw_audio_section(ui);
+            }
+
+            ui.add_space(40.0);
+            self.draw_filter_string_section(ui);
+            self.draw_compare_section(ui);
+            self.draw_weighting_section(ui);
+            self.draw_quantization_section(ui);
+            self.draw_titles_section(ui);
+            self.draw_display_section(ui);
+
+            #[cfg(debug_assertions)]
+            {
+                ui.add_space(40.0);
+                self.draw_debug_section(ui);
+            }
+        });
+    }
+
+    fn draw_filter_params_section(&mut self, ui: &mut egui::Ui) {
+        let warnings = self.filter_def.warnings();
+        let warning = |w: FilterWarning| warnings.iter().find(|warning| **warning == w);
+
+        let mut parameter_ids = Vec::new();
+
+        egui::Grid::new("filter").show(ui, |ui| {
+            App::draw_param_label(
+                ui,
+                "Sampling Frequency (Hz):",
+                SAMPLING_FREQUENCY_TOOLTIP,
+                None,
+            );
+            parameter_ids.push(
+                ui.add(
+                    egui::DragValue::new(&mut self.filter_def.f_sampling)
+                        .speed(0.1)
+                        .clamp_range(0.0..=f64::NAN)
+                        .max_decimals(0),
+                )
+                .id,
+            );
+            ui.end_row();
+
+            App::draw_param_label(
+                ui,
+                "Filter Length (Samples):",
+                LENGTH_TOOLTIP,
+                warning(FilterWarning::ShortLength),
+            );
+            parameter_ids.push(
+                ui.add(
+                    egui::DragValue::new(&mut self.filter_def.len)
+                        .speed(0.1)
+                        .max_decimals(0),
+                )
+                .id,
+            );
+            ui.end_row();
+
+            App::draw_param_label(
+                ui,
+                "Filter Shift (Samples):",
+                SHIFT_TOOLTIP,
+                warning(FilterWarning::NonLinearPhase),
+            );
+            parameter_ids.push(
+                ui.add(
+                    egui::DragValue::new(&mut self.filter_def.shift)
+                        .speed(0.1)
+                        .max_decimals(0),
+                )
+                .id,
+            );
+            ui.end_row();
+
+            App::draw_param_label(ui, "Filter Type:", FILTER_TYPE_TOOLTIP, None);
+            egui::ComboBox::from_id_source("filter")
+                .selected_text(format!("{}", self.filter_def.filter))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter_def.filter, Filter::LowPass, "Low Pass");
+                    ui.selectable_value(&mut self.filter_def.filter, Filter::HighPass, "High Pass");
+                    ui.selectable_value(&mut self.filter_def.filter, Filter::BandPass, "Band Pass");
+                    ui.selectable_value(&mut self.filter_def.filter, Filter::BandStop, "Band Stop");
+                    let is_multiband = matches!(self.filter_def.filter, Filter::Multiband { .. });
+                    if ui.selectable_label(is_multiband, "Multiband").clicked() && !is_multiband {
+                        self.filter_def.filter = Filter::Multiband {
+                            bands: self.default_bands(),
+                        };
+                    }
+                    let is_custom = matches!(self.filter_def.filter, Filter::Custom { .. });
+                    if ui.selectable_label(is_custom, "Custom").clicked() && !is_custom {
+                        self.filter_def.filter = Filter::Custom {
+                            expression: fir::DEFAULT_CUSTOM_EXPRESSION.to_string(),
+                        };
+                    }
+                })
+                .response
+                .on_hover_ui(|ui| {
+                    let formula =
+                        egui::RichText::new(self.filter_def.formula_display()).monospace();
+                    ui.add(egui::Label::new(formula));
+                });
+            ui.end_row();
+
+            if let Filter::Custom { expression } = &mut self.filter_def.filter {
+                App::draw_param_label(ui, "Expression h[n]:", EXPRESSION_TOOLTIP, None);
+                ui.vertical(|ui| {
+                    ui.add(egui::TextEdit::singleline(expression).code_editor())
+                        .on_hover_text(
+                            "Variables: n, shift, dt, f_lo_cut, f_hi_cut, PI\n\
+                             Functions: sin, cos, tan, exp, ln, sqrt, abs, sinc",
+                        );
+                    if let Err(e) = fir::compile_custom_filter(expression) {
+                        ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", e));
+                    }
+                });
+                ui.end_row();
+            }
+
+            match &mut self.filter_def.filter {
+                Filter::Multiband { bands } => {
+                    App::draw_band_rows(ui, bands, self.filter_def.f_sampling);
+                }
+                Filter::LowPass => {
+                    App::draw_param_label(
+                        ui,
+                        "High Cut Frequency (Hz):",
+                        HI_CUT_TOOLTIP,
+                        warning(FilterWarning::HighCutAboveNyquist),
+                    );
+                    parameter_ids.push(
+                        ui.add(
+                            egui::DragValue::new(&mut self.filter_def.f_hi_cut)
+                                .speed(0.1)
+                                .clamp_range(0.0..=f64::NAN)
+                                .max_decimals(0),
+                        )
+                        .id,
+                    );
+                }
+                _ => {
+                    App::draw_param_label(ui, "Low Cut Frequency (Hz):", LO_CUT_TOOLTIP, None);
+                    parameter_ids.push(
+                        ui.add(
+                            egui::DragValue::new(&mut self.filter_def.f_lo_cut)
+                                .speed(0.1)
+                                .clamp_range(0.0..=f64::NAN)
+                                .max_decimals(0),
+                        )
+                        .id,
+                    );
+                }
+            };
+            ui.end_row();
+
+            match self.filter_def.filter {
+                Filter::HighPass | Filter::LowPass | Filter::Multiband { .. } => (),
+                _ => {
+                    App::draw_param_label(
+                        ui,
+                        "High Cut Frequency (Hz):",
+                        HI_CUT_TOOLTIP,
+                        warning(FilterWarning::HighCutAboveNyquist),
+                    );
+                    parameter_ids.push(
+                        ui.add(
+                            egui::DragValue::new(&mut self.filter_def.f_hi_cut)
+                                .speed(0.1)
+                                .clamp_range(0.0..=f64::NAN)
+                                .max_decimals(0),
+                        )
+                        .id,
+                    );
+                    ui.end_row();
+                }
+            };
+
+            App::draw_param_label(ui, "Window Type:", WINDOW_TOOLTIP, None);
+            let base_window = match &mut self.filter_def.window {
+                Window::Modified { base, .. } => base.as_mut(),
+                window => window,
+            };
+            ui.horizontal(|ui| {
+                App::draw_window_combo_box(ui, base_window, "window_type");
+                if ui.button("Recommend").clicked() {
+                    self.show_recommendations = true;
+                }
+            });
+            ui.end_row();
+
+            App::draw_param_label(ui, "Window Modifier:", WINDOW_MODIFIER_TOOLTIP, None);
+            App::draw_window_modifier_combo_box(ui, &mut self.filter_def.window);
+            ui.end_row();
+
+            if let Window::Modified {
+                modifier: WindowModifier::Convolved(other),
+                ..
+            } = &mut self.filter_def.window
+            {
+                App::draw_param_label(ui, "Convolved With:", CONVOLVED_WINDOW_TOOLTIP, None);
+                App::draw_window_combo_box(ui, other, "convolved_window_type");
+                ui.end_row();
+            }
+
+            let base_window = match &mut self.filter_def.window {
+                Window::Modified { base, .. } => base.as_mut(),
+                window => window,
+            };
+
+            if let Window::Planck { epsilon } = base_window {
+                App::draw_param_label(ui, "Taper Fraction (ε):", EPSILON_TOOLTIP, None);
+                parameter_ids.push(
+                    ui.add(
+                        egui::DragValue::new(epsilon)
+                            .speed(0.001)
+                            .clamp_range(0.0..=0.5)
+                            .max_decimals(3),
+                    )
+                    .id,
+                );
+                ui.end_row();
+            }
+
+            if let Window::Dpss { half_bandwidth } = base_window {
+                App::draw_param_label(ui, "Half Bandwidth (W):", HALF_BANDWIDTH_TOOLTIP, None);
+                parameter_ids.push(
+                    ui.add(
+                        egui::DragValue::new(half_bandwidth)
+                            .speed(0.001)
+                            .clamp_range(0.001..=0.499)
+                            .max_decimals(3),
+                    )
+                    .id,
+                );
+                ui.end_row();
+            }
+
+            App::draw_param_label(ui, "Window Mode:", WINDOW_MODE_TOOLTIP, None);
+            ui.horizontal(|ui| {
+                for window_mode in [WindowMode::Symmetric, WindowMode::Periodic] {
+                    let text = format!("{}", window_mode);
+                    ui.selectable_value(&mut self.filter_def.window_mode, window_mode, text);
+                }
+            });
+            ui.end_row();
+
+            App::draw_param_label(ui, "Force Symmetry:", FORCE_SYMMETRY_TOOLTIP, None);
+            ui.checkbox(&mut self.filter_def.force_symmetry, "");
+            ui.end_row();
+
+            App::draw_param_label(ui, "Normalization:", NORMALIZATION_TOOLTIP, None);
+            egui::ComboBox::from_id_source("normalization")
+                .selected_text(format!("{}", self.filter_def.normalization))
+                .show_ui(ui, |ui| {
+                    for normalization in [
+                        Normalization::UnitPassbandGain,
+                        Normalization::UnitEnergy,
+                        Normalization::UnitSum,
+                    ] {
+                        let text = format!("{}", normalization);
+                        ui.selectable_value(
+                            &mut self.filter_def.normalization,
+                            normalization,
+                            text,
+                        );
+                    }
+                });
+        });
+
+        // Keep egui from moving the focus on Tab, App::navigate_parameters follows the grid instead.
+        // The filter has to be set after the text edit of the DragValue replaced it with its own.
+        if let Some(id) = ui.memory(|m| m.focus()) {
+            if parameter_ids.contains(&id) {
+                ui.memory_mut(|m| {
+                    m.set_focus_lock_filter(
+                        id,
+                        egui::EventFilter {
+                            tab: true,
+                            arrows: true,
+                            escape: false,
+                        },
+                    )
+                });
+            }
+        }
+        self.parameter_ids = parameter_ids;
+    }
+
+    /// Moves the keyboard focus between the parameter fields in grid order with Tab and Shift+Tab,
+    /// and applies the edited field with Ctrl+Enter.
+    fn navigate_parameters(&mut self, ctx: &egui::Context) {
+        let Some(id) = ctx.memory(|m| m.focus()) else {
+            return;
+        };
+        let Some(position) = self.parameter_ids.iter().position(|p| *p == id) else {
+            return;
+        };
+
+        let n_ids = self.parameter_ids.len();
+        let (next, previous, apply) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                i.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter),
+            )
+        });
+        if next {
+            ctx.memory_mut(|m| m.request_focus(self.parameter_ids[(position + 1) % n_ids]));
+        } else if previous {
+            ctx.memory_mut(|m| m.request_focus(self.parameter_ids[(position + n_ids - 1) % n_ids]));
+        } else if apply {
+            // The value is already updated while typing, leaving the field shows the result
+            ctx.memory_mut(|m| m.surrender_focus(id));
+        }
+    }
+
+    fn draw_plot_params_section(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("plot_params").show(ui, |ui| {
+            ui.label("Plot Response:");
+            egui::ComboBox::from_id_source("plot_type")
+                .selected_text(format!("{}", self.plot_type))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.plot_type, PlotType::Impulse, "Impulse");
+                    ui.selectable_value(&mut self.plot_type, PlotType::Step, "Step");
+                });
+            ui.end_row();
+
+            if self.plot_type == PlotType::Impulse {
+                ui.label("Impulse Style:");
+                let is_discrete_prev = self.time_response_style.is_discrete();
+                egui::ComboBox::from_id_source("time_response_style")
+                    .selected_text(format!("{}", self.time_response_style))
+                    .show_ui(ui, |ui| {
+                        for style in [
+                            TimeResponseStyle::Connected,
+                            TimeResponseStyle::Stem,
+                            TimeResponseStyle::Bar,
+                        ] {
+                            let text = format!("{}", style);
+                            ui.selectable_value(&mut self.time_response_style, style, text);
+                        }
+                    });
+                // The x axis switches between time and sample index
+                if self.time_response_style.is_discrete() != is_discrete_prev {
+                    self.reset_time_zoom = true;
+                }
+                ui.end_row();
+            }
+
+            ui.label("Lock Y Axis:");
+            ui.checkbox(&mut self.lock_y_axis, "");
+            ui.end_row();
+
+            if self.lock_y_axis {
+                ui.label("Y Axis Range:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.time_y_range[0]).speed(0.0001));
+                    ui.add(egui::DragValue::new(&mut self.time_y_range[1]).speed(0.0001));
+                    if ui.button("Auto").clicked() {
+                        self.lock_y_axis = false;
+                        self.reset_time_zoom = true;
+                    }
+                });
+                ui.end_row();
+            }
+
+            ui.label("Normalize Amplitude:");
+            ui.checkbox(&mut self.normalize_amplitude, "");
+            ui.end_row();
+
+            ui.label("Color Theme:");
+            let color_theme_prev = self.color_theme.is_light();
+            egui::ComboBox::from_id_source("color_theme")
+                .selected_text(format!("{}", self.color_theme))
+                .show_ui(ui, |ui| {
+                    for color_theme in [
+                        ColorTheme::Default,
+                        ColorTheme::Colorblind,
+                        ColorTheme::Grayscale,
+                        ColorTheme::Publication,
+                    ] {
+                        let text = format!("{}", color_theme);
+                        ui.selectable_value(&mut self.color_theme, color_theme, text);
+                    }
+                });
+            if self.color_theme.is_light() != color_theme_prev {
+                ui.ctx().set_visuals(if self.color_theme.is_light() {
+                    egui::Visuals::light()
+                } else {
+                    egui::Visuals::dark()
+                });
+            }
+            ui.end_row();
+
+            ui.label("Normalize Axis to Nyquist:");
+            if ui.checkbox(&mut self.normalize_nyquist, "").changed() {
+                self.reset_freq_zoom = true;
+            }
+            ui.end_row();
+
+            ui.label("Apply Window Correction:");
+            ui.checkbox(&mut self.window_correction, "")
+                .on_hover_text("Divide the windowed response by the coherent gain of the window");
+            ui.end_row();
+
+            ui.label("Decimation Factor:");
+            ui.add(egui::DragValue::new(&mut self.decimation_factor).clamp_range(1..=64))
+                .on_hover_text("Show the spectrum after keeping every M-th sample of the output");
+            ui.end_row();
+
+            ui.label("Show PSD:");
+            ui.checkbox(&mut self.show_psd, "");
+            ui.end_row();
+
+            ui.label("Show Minimum Phase:");
+            ui.checkbox(&mut self.show_min_phase, "");
+            ui.end_row();
+
+            ui.label("Show Window:");
+            ui.checkbox(&mut self.show_window, "");
+            ui.end_row();
+
+            ui.label("Show Autocorrelation:");
+            ui.checkbox(&mut self.show_autocorrelation, "");
+            ui.end_row();
+
+            ui.label("Show Phase:");
+            ui.checkbox(&mut self.show_phase, "");
+            ui.end_row();
+
+            ui.label("Show Pole-Zero Plot:");
+            ui.checkbox(&mut self.show_pole_zero, "");
+            ui.end_row();
+
+            ui.label("Lock Aspect Ratio:");
+            ui.checkbox(&mut self.lock_aspect_ratio, "");
+            ui.end_row();
+
+            ui.label("Ratio:");
+            egui::ComboBox::from_id_source("aspect_ratio")
+                .selected_text(format!("{}", self.aspect_ratio))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.aspect_ratio, AspectRatio::Widescreen, "16:9");
+                    ui.selectable_value(&mut self.aspect_ratio, AspectRatio::Standard, "4:3");
+                    ui.selectable_value(&mut self.aspect_ratio, AspectRatio::Photo, "3:2");
+                    ui.selectable_value(&mut self.aspect_ratio, AspectRatio::Square, "1:1");
+                });
+            ui.end_row();
+        });
+
+        if ui
+            .button("Reset All Zoom")
+            .on_hover_text("Fit all plots to their data, also Home or a double click on a plot")
+            .clicked()
+        {
+            self.reset_all_zoom();
+        }
+    }
+
+    /// Fits all plots to their data again after zooming or panning.
+    fn reset_all_zoom(&mut self) {
+        self.reset_time_zoom = true;
+        self.reset_freq_zoom = true;
+        self.reset_window_zoom = true;
+    }
+
+    fn draw_metrics_section(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("metrics").show(ui, |ui| {
+            ui.label("Normalization:");
+            ui.label(format!("{}", self.filter_def.normalization));
+            ui.end_row();
+
+            ui.label("3 dB Bandwidth (Hz):");
+            ui.label(format!("{:.1}", self.filter_data.bandwidth_3db));
+            ui.end_row();
+
+            let metrics = &self.filter_data.metrics;
+            let theoretical_3db_hz = self.filter_def.theoretical_3db_hz();
+            ui.label("Theoretical -3 dB (Hz):");
+            ui.label(format!("{:.1}", theoretical_3db_hz));
+            ui.end_row();
+
+            ui.label("Actual -3 dB (Hz):");
+            ui.label(format!(
+                "{:.1} ({:+.1})",
+                metrics.actual_3db_hz,
+                metrics.actual_3db_hz - theoretical_3db_hz
+            ));
+            ui.end_row();
+
+            ui.label("Passband Ripple (dB):");
+            ui.label(format!("{:.2}", metrics.passband_ripple_db));
+            ui.end_row();
+
+            ui.label("Passband Droop (dB):");
+            ui.label(format!("{:.3}", metrics.passband_droop_db));
+            ui.end_row();
+
+            ui.label("Stopband Attenuation (dB):");
+            ui.label(format!("{:.1}", metrics.stopband_attenuation_db));
+            ui.end_row();
+
+            let is_highpass = self.filter_def.filter == Filter::HighPass;
+            ui.label("Passband:");
+            match self.filter_data.passband_end_hz {
+                Some(f_edge) if is_highpass => ui.label(format!(
+                    "{:.1} – {:.1} Hz (±{} dB)",
+                    f_edge,
+                    self.filter_def.f_sampling / 2.0,
+                    data::SPEC_PASSBAND_RIPPLE_DB
+                )),
+                Some(f_edge) if self.filter_def.is_passband(0.0) => ui.label(format!(
+                    "0 – {:.1} Hz (±{} dB)",
+                    f_edge,
+                    data::SPEC_PASSBAND_RIPPLE_DB
+                )),
+                Some(f_edge) => ui.label(format!(
+                    "up to {:.1} Hz (±{} dB)",
+                    f_edge,
+                    data::SPEC_PASSBAND_RIPPLE_DB
+                )),
+                None => ui.label(format!("exceeds ±{} dB", data::SPEC_PASSBAND_RIPPLE_DB)),
+            };
+            ui.end_row();
+
+            ui.label("Stopband:");
+            match self.filter_data.stopband_start_hz {
+                Some(f_edge) => ui.label(format!(
+                    "{} {:.1} Hz (≤ −{} dB)",
+                    if is_highpass { "up to" } else { "from" },
+                    f_edge,
+                    data::SPEC_STOPBAND_ATTENUATION_DB
+                )),
+                None => ui.label(format!(
+                    "never reaches −{} dB",
+                    data::SPEC_STOPBAND_ATTENUATION_DB
+                )),
+            };
+            ui.end_row();
+
+            ui.label("Transition Width (Hz):");
+            ui.label(format!("{:.1}", metrics.transition_width_hz));
+            ui.end_row();
+
+            ui.label("L2 Response Error (√Hz):");
+            ui.label(format!("{:.3}", metrics.l2_error));
+            ui.end_row();
+
+            ui.label("L∞ Response Error:");
+            ui.label(format!("{:.4}", metrics.inf_error));
+            ui.end_row();
+
+            ui.label("Equivalent Noise Bandwidth (Hz):");
+            ui.label(format!("{:.1}", metrics.equivalent_noise_bandwidth_hz));
+            ui.end_row();
+
+            ui.label("DC Gain (dB):");
+            ui.label(format!("{:.2}", metrics.dc_gain_db));
+            ui.end_row();
+
+            if self.filter_def.filter == Filter::HighPass {
+                let text = format!("{:.2}", metrics.nyquist_gain_db);
+                ui.label("Nyquist Gain (dB):");
+                if metrics.nyquist_gain_db.abs() > NYQUIST_GAIN_TOLERANCE_DB {
+                    ui.colored_label(egui::Color32::RED, format!("⚠ {}", text));
+                } else {
+                    ui.label(text);
+                }
+                ui.end_row();
+            }
+
+            ui.label("FIR Type:");
+            match metrics.fir_type {
+                Some(fir_type) => ui.label(format!("{}", fir_type)),
+                None => ui.label("Nonlinear Phase"),
+            };
+            ui.end_row();
+
+            ui.label("Symmetry Error:");
+            ui.label(format!("{:.2e}", metrics.symmetry_error));
+            ui.end_row();
+
+            ui.label("Max Coefficient:");
+            ui.label(format!("{:.4e}", metrics.max_coefficient));
+            ui.end_row();
+
+            ui.label("Min Coefficient:");
+            ui.label(format!("{:.4e}", metrics.min_coefficient));
+            ui.end_row();
+
+            ui.label("Dynamic Range (dB):");
+            ui.label(format!("{:.1}", metrics.dynamic_range_db));
+            ui.end_row();
+
+            ui.label("Required Fractional Bits:");
+            ui.label(format!("{}", metrics.required_fractional_bits));
+            ui.end_row();
+
+            let latency_samples = self.filter_def.causality_delay_samples();
+            ui.label("Latency:");
+            let latency = format!(
+                "{} samples = {:.3} ms",
+                latency_samples,
+                latency_samples as f64 / self.filter_def.f_sampling * 1000.0
+            );
+            if self.filter_def.is_causal() {
+                ui.label(latency);
+            } else {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {} (not causal)", latency));
+            }
+            ui.end_row();
+
+            ui.label("Group Delay at Passband Center (Samples):");
+            ui.label(format!("{:.2}", metrics.group_delay_samples));
+            ui.end_row();
+
+            ui.label("Group Delay Deviation (Samples):");
+            ui.label(format!("{:.2}", metrics.group_delay_deviation_samples));
+            ui.end_row();
+
+            ui.label("Group Delay Variation:");
+            let text = format!("{:.1} dB", metrics.group_delay_variation_db);
+            if metrics.group_delay_variation_db > GROUP_DELAY_VARIATION_LIMIT_DB {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {}", text));
+            } else {
+                ui.label(text);
+            }
+            ui.end_row();
+
+            if let Some(f_min_phase) = self.visible_min_phase() {
+                ui.label("Minimum Phase Group Delay (Samples):");
+                ui.label(format!(
+                    "{:.2}",
+                    FilterDef::compute_group_delay_at(
+                        f_min_phase,
+                        self.filter_def.passband_center_hz(),
+                        self.filter_def.f_sampling
+                    )
+                ));
+                ui.end_row();
+            }
+
+            ui.label("Coefficient Sensitivity (16 Bit LSB):");
+            ui.label(format!("{:.2e}", self.filter_data.coefficient_sensitivity));
+            ui.end_row();
+
+            ui.label("Recommended Word Length (Bits):");
+            ui.label(format!("{}", self.filter_data.word_length));
+            ui.end_row();
+
+            let quantization = &self.filter_data.quantization_16bit;
+            ui.label(egui::RichText::new("16-bit Quantization Impact").strong());
+            ui.end_row();
+
+            ui.label("Error Response (dB):");
+            ui.label(format!("{:.1}", quantization.worst_case_magnitude_error_db));
+            ui.end_row();
+
+            ui.label("Passband Error (dB):");
+            ui.label(format!("{:.4}", quantization.worst_case_passband_error_db));
+            ui.end_row();
+
+            ui.label("Stopband Attenuation Loss (dB):");
+            ui.label(format!("{:.2}", quantization.worst_case_stopband_error_db));
+            ui.end_row();
+
+            ui.label(format!(
+                "Significant Taps (> {:.0} dB):",
+                20.0 * data::SIGNIFICANT_TAP_THRESHOLD.log10()
+            ));
+            ui.label(format!(
+                "{} of {}",
+                self.filter_data.significant_taps,
+                self.filter_data.f_windowed.len()
+            ));
+            ui.end_row();
+
+            ui.label("Sparse Taps:");
+            let mut text = format!(
+                "{} of {}",
+                self.filter_data.sparse_taps,
+                self.filter_data.f_windowed.len()
+            );
+            // Nothing is dropped if every tap is significant
+            if self.filter_data.sparse_error_bound_db.is_finite() {
+                text.push_str(&format!(
+                    " (Error < {:.0} dB)",
+                    self.filter_data.sparse_error_bound_db
+                ));
+            }
+            ui.label(text);
+            ui.end_row();
+
+            if self.filter_data.aliasing_artifact.is_finite() {
+                let text = format!("{:.1}", self.filter_data.aliasing_artifact);
+                ui.label("Aliasing Artifact (dB):");
+                if self.filter_data.aliasing_artifact > ALIASING_ARTIFACT_LIMIT_DB {
+                    ui.colored_label(egui::Color32::RED, text);
+                } else {
+                    ui.label(text);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(error) = &self.filter_error {
+            ui.colored_label(egui::Color32::RED, format!("✖ {}", error));
+        }
+        for warning in self.filter_def.warnings() {
+            ui.label(egui::RichText::new(format!("⚠ {}", warning)).color(egui::Color32::YELLOW));
+        }
+        if self.filter_def.is_type_ii() {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "⚠ Even filter length detected: Type II FIR has Nyquist null.",
+                    )
+                    .color(egui::Color32::YELLOW),
+                );
+                if ui.button("Make Odd").clicked() {
+                    self.filter_def.ensure_odd_length();
+                }
+            });
+        }
+    }
+
+    fn draw_file_section(&mut self, ui: &mut egui::Ui) {
+        ui.with_layout(
+            egui::Layout::top_down_justified(egui::Align::Center),
+            |ui| {
+                if ui.button("Export Filter").clicked() {
+                    println! {"{:?}", self.filter_data.f_windowed};
+                }
+
+                ui.add_enabled_ui(!self.recent_designs.is_empty(), |ui| {
+                    ui.menu_button("Recent Designs", |ui| {
+                        let mut selected = None;
+                        for (description, filter_def) in &self.recent_designs {
+                            if ui.button(description).clicked() {
+                                selected = Some(filter_def.clone());
+                                ui.close_menu();
+                            }
+                        }
+                        if let Some(filter_def) = selected {
+                            self.add_recent_design(&filter_def);
+                            self.filter_def = filter_def;
+                        }
+                    });
+                });
+
+                if ui.button("Export JSON").clicked() {
+                    let json = export::to_json_full(
+                        &self.filter_def,
+                        &self.filter_data,
+                        env!("CARGO_PKG_VERSION"),
+                    );
+                    println!("{:#}", json);
+                    self.mark_saved();
+                }
+
+                if ui.button("Export as Markdown").clicked() {
+                    print!(
+                        "{}",
+                        export::to_markdown_table(&self.filter_def, &self.filter_data.metrics)
+                    );
+                }
+                if ui.button("Export Frequency Response").clicked() {
+                    self.export_frequency_response();
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Save Binary").clicked() {
+                        self.binary_status = Some(
+                            match self.filter_def.to_binary_file(Path::new(BINARY_PATH)) {
+                                Ok(()) => {
+                                    self.mark_saved();
+                                    format!("Saved {}", BINARY_PATH)
+                                }
+                                Err(e) => format!("⚠ {}: {}", BINARY_PATH, e),
+                            },
+                        );
+                    }
+                    if ui.button("Load Binary").clicked() {
+                        self.binary_status =
+                            Some(match FilterDef::from_binary_file(Path::new(BINARY_PATH)) {
+                                Ok(filter_def) => {
+                                    self.add_recent_design(&filter_def);
+                                    self.filter_def = filter_def;
+                                    format!("Loaded {}", BINARY_PATH)
+                                }
+                                Err(e) => format!("⚠ {}: {}", BINARY_PATH, e),
+                            });
+                    }
+                });
+                if let Some(status) = &self.binary_status {
+                    ui.label(status);
+                }
+
+                if ui.button("Export JSON Lines").clicked() {
+                    print!("{}", export::to_jsonlines(&self.filter_data.f_windowed));
+                }
+
+                if ui.button("Import JSON Lines").clicked() {
+                    self.import_jsonlines();
+                }
+                if let Some(status) = &self.jsonlines_status {
+                    ui.label(status);
+                }
+
+                if ui.button("Export for LabVIEW").clicked() {
+                    print!(
+                        "{}",
+                        export::to_labview_array_string(
+                            &self.filter_data.f_windowed,
+                            &self.filter_def
+                        )
+                    );
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Export as Rust const").clicked() {
+                        print!(
+                            "{}",
+                            export::to_rust_const_array(
+                                &self.filter_data.f_windowed,
+                                "fir_coefficients",
+                                self.rust_const_precision
+                            )
+                        );
+                    }
+                    egui::ComboBox::from_id_source("rust_const_precision")
+                        .selected_text(format!("{}", self.rust_const_precision))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.rust_const_precision,
+                                Precision::F32,
+                                "f32",
+                            );
+                            ui.selectable_value(
+                                &mut self.rust_const_precision,
+                                Precision::F64,
+                                "f64",
+                            );
+                        });
+                });
+
+                ui.checkbox(&mut self.zero_phase_output, "Zero-Phase Output");
+                ui.horizontal(|ui| {
+                    if ui.button("Export Test Vectors").clicked() {
+                        self.export_test_vectors();
+                    }
+                    if ui.button("Chirp Test").clicked() {
+                        self.show_chirp_test = true;
+                    }
+                    if ui.button("Design Space").clicked() {
+                        self.show_design_space = true;
+                    }
+                });
+
+                ui.text_edit_singleline(&mut self.signal_path);
+                ui.horizontal(|ui| {
+                    if ui.button("Import Signal").clicked() {
+                        self.import_signal();
+                    }
+                    if ui.button("Export Filtered Signal").clicked() {
+                        self.export_filtered_signal();
+                    }
+                });
+                if let Some(status) = &self.signal_status {
+                    ui.label(status);
+                }
+
+                if ui.button("Save Plots").clicked() {};
+
+                self.draw_reset_button(ui);
+
+                ui.text_edit_singleline(&mut self.target_path);
+                ui.horizontal(|ui| {
+                    if ui.button("Load Target Response").clicked() {
+                        self.load_target_response();
+                    }
+                    if ui.button("Clear Target").clicked() {
+                        self.target_response = None;
+                        self.f_matched_dft = None;
+                        self.target_error = None;
+                    }
+                });
+                if let Some(error) = &self.target_error {
+                    ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", error));
+                }
+
+                ui.text_edit_singleline(&mut self.cascade_path);
+                ui.horizontal(|ui| {
+                    if ui.button("Cascade with Loaded Design").clicked() {
+                        self.load_cascade_design();
+                    }
+                    if ui.button("Clear Cascade").clicked() {
+                        self.cascade_def = None;
+                        self.cascade_data = None;
+                        self.cascade_error = None;
+                    }
+                });
+                if let Some(error) = &self.cascade_error {
+                    ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", error));
+                }
+            },
+        );
+    }
+
+    fn draw_reset_button(&mut self, ui: &mut egui::Ui) {
+        if !self.confirm_reset {
+            if ui.button("Reset to Defaults").clicked() {
+                self.confirm_reset = true;
+            }
+            return;
+        }
+
+        if self.filter_def != self.saved_filter_def {
+            ui.label(
+                egui::RichText::new("⚠ The current design has unsaved changes!")
+                    .strong()
+                    .color(egui::Color32::RED),
+            );
+        }
+        ui.label("Are you sure? Reset will clear all parameters.");
+        ui.horizontal(|ui| {
+            if ui.button("Reset").clicked() {
+                self.reset_to_defaults(ui.ctx());
+                self.confirm_reset = false;
+            }
+            if ui.button("Cancel").clicked() {
+                self.confirm_reset = false;
+            }
+        });
+    }
+
+    /// Restores the filter parameters and plot settings of a fresh start.
+    fn reset_to_defaults(&mut self, ctx: &egui::Context) {
+        self.filter_def = App::default_filter_def();
+        self.plot_type = PlotType::default();
+        self.time_response_style = TimeResponseStyle::default();
+        self.show_window = true;
+        self.normalize_amplitude = false;
+        self.show_autocorrelation = false;
+        self.show_phase = false;
+        self.show_pole_zero = false;
+        self.show_chirp_test = false;
+        self.show_design_space = false;
+        self.high_res_band_edge = false;
+        self.band_edge_response = None;
+        self.show_recommendations = false;
+        self.show_psd = false;
+        self.show_min_phase = false;
+        self.lock_aspect_ratio = false;
+        self.aspect_ratio = AspectRatio::default();
+        self.lock_y_axis = false;
+        self.time_y_range = [0.0, 1.0];
+        self.normalize_nyquist = false;
+        self.window_correction = false;
+        self.decimation_factor = 1;
+        self.color_theme = ColorTheme::default();
+        ctx.set_visuals(egui::Visuals::dark());
+        self.plot_titles = DEFAULT_PLOT_TITLES.map(String::from);
+        self.plot_x_labels = DEFAULT_X_LABELS.map(String::from);
+        self.plot_y_labels = Default::default();
+        self.reset_freq_zoom = true;
+        self.reset_time_zoom = true;
+    }
+
+    /// Reads coefficients from the JSON Lines file and reports how they compare to the current
+    /// design.
+    fn import_jsonlines(&mut self) {
+        let coefficients = std::fs::read_to_string(JSONLINES_PATH)
+            .map_err(|e| e.to_string())
+            .and_then(|s| export::from_jsonlines(&s).map_err(|e| e.to_string()));
+
+        self.jsonlines_status = Some(match coefficients {
+            Ok(coefficients) if coefficients.len() == self.filter_data.f_windowed.len() => {
+                let max_diff = coefficients
+                    .iter()
+                    .zip(&self.filter_data.f_windowed)
+                    .fold(0.0, |max: f64, (a, b)| max.max((a - b).abs()));
+                format!(
+                    "Read {} coefficients, max. difference {:.2e}",
+                    coefficients.len(),
+                    max_diff
+                )
+            }
+            Ok(coefficients) => format!(
+                "Read {} coefficients, the design has {}",
+                coefficients.len(),
+                self.filter_data.f_windowed.len()
+            ),
+            Err(e) => format!("⚠ {}: {}", JSONLINES_PATH, e),
+        });
+    }
+
+    /// Reads a signal from a CSV file, or a WAV file when built with the `wav` feature.
+    fn import_signal(&mut self) {
+        let path = Path::new(&self.signal_path);
+        let is_wav = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+
+        let signal = if is_wav {
+            App::read_wav(path)
+        } else {
+            std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|csv| export::from_csv(&csv).map_err(|e| e.to_string()))
+                .map(|signal| (signal, None))
+        };
+
+        match signal {
+            Ok((signal, sample_rate)) => {
+                let mut status = format!("Read {} samples", signal.len());
+                if let Some(sample_rate) = sample_rate {
+                    if sample_rate as f64 != self.filter_def.f_sampling {
+                        status = format!(
+                            "⚠ {}, the file sample rate is {} Hz, the filter is designed for {} Hz",
+                            status, sample_rate, self.filter_def.f_sampling
+                        );
+                    }
+                }
+
+                self.signal = Some(signal);
+                self.signal_sample_rate = sample_rate;
+                self.signal_status = Some(status);
+            }
+            Err(e) => self.signal_status = Some(format!("⚠ {}: {}", self.signal_path, e)),
+        }
+    }
+
+    #[cfg(feature = "wav")]
+    fn read_wav(path: &Path) -> Result<(Vec<f64>, Option<u32>), String> {
+        fir_designer::wav::read_wav(path)
+            .map(|(signal, sample_rate)| (signal, Some(sample_rate)))
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(feature = "wav"))]
+    fn read_wav(_path: &Path) -> Result<(Vec<f64>, Option<u32>), String> {
+        Err("WAV files require building with the wav feature".to_string())
+    }
+
+    /// Filters the imported signal and writes it next to the input file with a `filtered_` prefix,
+    /// in the same format as the input.
+    fn export_filtered_signal(&mut self) {
+        let Some(signal) = &self.signal else {
+            self.signal_status = Some("⚠ No signal imported".to_string());
+            return;
+        };
+
+        let output = if self.zero_phase_output {
+            FilterDef::zero_phase_filter(&self.filter_data.f_windowed, signal)
+        } else {
+            FilterDef::apply_overlap_add(&self.filter_data.f_windowed, signal, fir::DFT_LEN)
+        };
+
+        let path = Path::new(&self.signal_path);
+        let file_name = path
+            .file_name()
+            .map_or("signal.csv".into(), |name| name.to_string_lossy());
+        let path = path.with_file_name(format!("filtered_{}", file_name));
+
+        let result = match self.signal_sample_rate {
+            #[cfg(feature = "wav")]
+            Some(sample_rate) => {
+                fir_designer::wav::write_wav(&path, &output, sample_rate).map_err(|e| e.to_string())
+            }
+            _ => std::fs::write(&path, export::to_csv(&output, "filtered"))
+                .map_err(|e| e.to_string()),
+        };
+
+        self.signal_status = Some(match result {
+            Ok(()) => format!("Wrote {}", path.display()),
+            Err(e) => format!("⚠ {}: {}", path.display(), e),
+        });
+    }
+
+    fn load_target_response(&mut self) {
+        let response = std::fs::read_to_string(&self.target_path)
+            .map_err(|e| e.to_string())
+            .and_then(|csv| export::from_csv_response(&csv).map_err(|e| e.to_string()));
+
+        match response {
+            Ok(response) => {
+                self.target_response = Some(response);
+                self.target_error = None;
+                self.compute_matched_response();
+            }
+            Err(e) => self.target_error = Some(format!("{}: {}", self.target_path, e)),
+        }
+    }
+
+    /// Loads a design exported with "Export JSON" to cascade with the current design.
+    fn load_cascade_design(&mut self) {
+        let cascade_def = std::fs::read_to_string(&self.cascade_path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+            .and_then(|json| export::from_json_full(&json, false).map_err(|e| e.to_string()));
+
+        match cascade_def {
+            Ok(cascade_def) => {
+                self.cascade_def = Some(cascade_def);
+                self.cascade_error = None;
+                self.compute_cascade();
+            }
+            Err(e) => self.cascade_error = Some(format!("{}: {}", self.cascade_path, e)),
+        }
+    }
+
+    fn compute_filter_data(&mut self) {
+        match FilterData::try_from(&self.filter_def) {
+            Ok(filter_data) => {
+                self.filter_data = filter_data;
+                self.filter_error = None;
+            }
+            Err(error) => {
+                self.filter_data = FilterData::default();
+                self.filter_error = Some(error);
+            }
+        }
+    }
+
+    fn compute_cascade(&mut self) {
+        self.cascade_data = None;
+        let Some(cascade_def) = &self.cascade_def else {
+            return;
+        };
+
+        match FilterData::try_from(cascade_def) {
+            Ok(cascade_data) => {
+                self.cascade_data = Some(FilterData::cascade(
+                    &self.filter_data,
+                    &cascade_data,
+                    self.filter_def.f_sampling,
+                ))
+            }
+            Err(e) => self.cascade_error = Some(format!("{}: {}", self.cascade_path, e)),
+        }
+    }
+
+    /// Designs the filter matching the target response with the selected window.
+    fn compute_matched_response(&mut self) {
+        self.f_matched_dft = self.target_response.as_ref().map(|target| {
+            let f = fir::design_to_match_response(
+                target,
+                self.filter_def.len,
+                self.filter_def.f_sampling,
+            );
+            let f_windowed =
+                FilterDef::compute_filter_windowed(&f, &self.filter_def.compute_window());
+
+            data::plot_dft(&f_windowed, self.filter_def.f_sampling).magnitude_db
+        });
+    }
+
+    /// Computes the response of the design interpolated between the reference and the current one.
+    fn compute_morph_response(&mut self) {
+        self.f_morph_dft = self.reference_def.as_ref().map(|reference_def| {
+            let morph_def = fir::interpolate(reference_def, &self.filter_def, self.morph_t);
+
+            data::plot_dft(&morph_def.compute_filter(), morph_def.f_sampling).magnitude_db
+        });
+    }
+
+    /// Computes the response of the weighted least squares design if it is shown.
+    fn compute_weighted_response(&mut self) {
+        self.f_weighted_dft = self.show_weighted.then(|| {
+            let f_weighted = self
+                .filter_def
+                .compute_filter_weighted(self.passband_weight, self.stopband_weight);
+
+            data::plot_dft(&f_weighted, self.filter_def.f_sampling).magnitude_db
+        });
+    }
+
+    #[cfg(feature = "audio")]
+    fn draw_audio_section(&mut self, ui: &mut egui::Ui) {
+        let mut monitor = self.audio_monitor.is_some();
+        if ui.toggle_value(&mut monitor, "Monitor Audio").changed() {
+            self.audio_monitor = None;
+            self.audio_error = None;
+            if monitor {
+                match AudioMonitor::start(self.filter_data.f_windowed.clone()) {
+                    Ok(audio_monitor) => self.audio_monitor = Some(audio_monitor),
+                    Err(e) => self.audio_error = Some(e),
+                }
+            }
+        }
+
+        if let Some(audio_monitor) = &self.audio_monitor {
+            egui::Grid::new("audio").show(ui, |ui| {
+                for (name, level) in ["Input:", "Output:"].iter().zip(audio_monitor.levels()) {
+                    ui.label(*name);
+                    ui.add(
+                        egui::ProgressBar::new(level.min(1.0))
+                            .text(format!("{:.1} dB", 20.0 * level.log10())),
+                    );
+                    ui.end_row();
+                }
+            });
+
+            if audio_monitor.sample_rate() as f64 != self.filter_def.f_sampling {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!(
+                        "⚠ Device sample rate is {} Hz, the filter is designed for {} Hz",
+                        audio_monitor.sample_rate(),
+                        self.filter_def.f_sampling
+                    ),
+                );
+            }
+
+            // Keep the level meters moving
+            ui.ctx().request_repaint();
+        }
+
+        if let Some(error) = &self.audio_error {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", error));
+        }
+    }
+
+    fn draw_filter_string_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Filter String").show(ui, |ui| {
+            let filter_string = self.filter_def.to_filter_string();
+            ui.add(egui::TextEdit::singleline(&mut filter_string.as_str()).code_editor());
+
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.filter_string_input);
+                if ui.button("Load").clicked() {
+                    match FilterDef::from_filter_string(&self.filter_string_input) {
+                        Ok(filter_def) => {
+                            self.add_recent_design(&filter_def);
+                            self.filter_def = filter_def;
+                            self.filter_string_error = None;
+                        }
+                        Err(e) => self.filter_string_error = Some(e.to_string()),
+                    }
+                }
+            });
+
+            if let Some(error) = &self.filter_string_error {
+                ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", error));
+            }
+        });
+    }
+
+    fn draw_compare_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Compare").show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Set as Reference").clicked() {
+                    self.reference_def = Some(self.filter_def.clone());
+                    self.compute_morph_response();
+                }
+                if ui.button("Clear").clicked() {
+                    self.reference_def = None;
+                    self.f_morph_dft = None;
+                }
+            });
+
+            if self.reference_def.is_some() {
+                ui.horizontal(|ui| {
+                    ui.label("Morph:");
+                    let slider = egui::Slider::new(&mut self.morph_t, 0.0..=1.0).text("t");
+                    if ui.add(slider).changed() {
+                        self.compute_morph_response();
+                    }
+                });
+            }
+
+            match &self.reference_def {
+                Some(reference_def) => {
+                    ui.label(format!("{}", diff::diff(reference_def, &self.filter_def)));
+                }
+                None => {
+                    ui.label("No reference design set");
+                }
+            }
+        });
     }
 
-    fn draw_window_combo_box(&mut self, ui: &mut egui::Ui) {
-        egui::ComboBox::from_id_source("window_type")
-            .selected_text(format!("{}", self.filter_def.window))
-            .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    &mut self.filter_def.window,
-                    Window::Rectangular,
-                    "Rectangular",
-                );
-                ui.selectable_value(
-                    &mut self.filter_def.window,
-                    Window::Triangular,
-                    "Triangular",
-                );
-                ui.selectable_value(&mut self.filter_def.window, Window::Welch, "Welch");
-                ui.selectable_value(&mut self.filter_def.window, Window::Sin, "Sin");
-                ui.selectable_value(&mut self.filter_def.window, Window::Hann, "Hann");
-                ui.selectable_value(&mut self.filter_def.window, Window::Hamming, "Hamming");
-                ui.selectable_value(&mut self.filter_def.window, Window::Blackman, "Blackman");
-                ui.selectable_value(&mut self.filter_def.window, Window::Nuttall, "Nutall");
-                ui.selectable_value(
-                    &mut self.filter_def.window,
-                    Window::BlackmanNuttall,
-                    "Blackman Nuttull",
-                );
-                ui.selectable_value(
-                    &mut self.filter_def.window,
-                    Window::BlackmanHarris,
-                    "Blackman Harris",
-                );
-                ui.selectable_value(&mut self.filter_def.window, Window::FlatTop, "Flat Top");
+    fn draw_weighting_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Band Weighting").show(ui, |ui| {
+            let mut changed = ui
+                .checkbox(&mut self.show_weighted, "Show Weighted Design")
+                .changed();
+
+            egui::Grid::new("band_weighting").show(ui, |ui| {
+                for (label, weight) in [
+                    ("Passband Weight:", &mut self.passband_weight),
+                    ("Stopband Weight:", &mut self.stopband_weight),
+                ] {
+                    ui.label(label);
+                    let slider = egui::Slider::new(weight, 0.01..=100.0).logarithmic(true);
+                    changed |= ui.add(slider).changed();
+                    ui.end_row();
+                }
             });
-    }
-}
 
-impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let filter_def_prev = self.filter_def.clone();
+            if changed {
+                self.compute_weighted_response();
+            }
+        });
+    }
 
-        egui::SidePanel::right("right_panel").show(ctx, |ui| {
-            ui.add_space(4.0);
-            ui.label("Filter Parameters");
-            ui.separator();
-            egui::Grid::new("filter").show(ui, |ui| {
-                ui.label("Sampling Frequency (Hz):");
-                ui.add(
-                    egui::DragValue::new(&mut self.filter_def.f_sampling)
-                        .speed(0.1)
-                        .clamp_range(0.0..=f64::NAN)
-                        .max_decimals(0),
-                );
+    fn draw_quantization_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Quantization Analysis").show(ui, |ui| {
+            egui::Grid::new("quantization_params").show(ui, |ui| {
+                ui.label("Bits:");
+                ui.add(egui::DragValue::new(&mut self.quantization_bits).clamp_range(8..=32));
                 ui.end_row();
 
-                ui.label("Filter Length (Samples):");
+                ui.label("Trials:");
                 ui.add(
-                    egui::DragValue::new(&mut self.filter_def.len)
-                        .speed(0.1)
-                        .max_decimals(0),
+                    egui::DragValue::new(&mut self.quantization_trials)
+                        .speed(10.0)
+                        .clamp_range(100..=10000),
                 );
                 ui.end_row();
+            });
 
-                ui.label("Filter Shift (Samples):");
-                ui.add(
-                    egui::DragValue::new(&mut self.filter_def.shift)
-                        .speed(0.1)
-                        .max_decimals(0),
-                );
-                ui.end_row();
+            if ui.button("Run").clicked() {
+                self.quantization_stats = Some(data::monte_carlo_quantization(
+                    &self.filter_def,
+                    self.quantization_bits,
+                    self.quantization_trials,
+                ));
+            }
 
-                ui.label("Filter Type:");
-                egui::ComboBox::from_id_source("filter")
-                    .selected_text(format!("{}", self.filter_def.filter))
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.filter_def.filter,
-                            Filter::LowPass,
-                            "Low Pass",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_def.filter,
-                            Filter::HighPass,
-                            "High Pass",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_def.filter,
-                            Filter::BandPass,
-                            "Band Pass",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter_def.filter,
-                            Filter::BandStop,
-                            "Band Stop",
-                        );
-                    });
-                ui.end_row();
+            if let Some(stats) = &self.quantization_stats {
+                egui::Grid::new("quantization_stats").show(ui, |ui| {
+                    ui.label("");
+                    ui.strong("Ripple (dB)");
+                    ui.strong("Attenuation (dB)");
+                    ui.end_row();
 
-                match self.filter_def.filter {
-                    Filter::LowPass => {
-                        ui.label("High Cut Frequency (Hz):");
-                        ui.add(
-                            egui::DragValue::new(&mut self.filter_def.f_hi_cut)
-                                .speed(0.1)
-                                .clamp_range(0.0..=f64::NAN)
-                                .max_decimals(0),
-                        );
-                    }
-                    _ => {
-                        ui.label("Low Cut Frequency (Hz):");
-                        ui.add(
-                            egui::DragValue::new(&mut self.filter_def.f_lo_cut)
-                                .speed(0.1)
-                                .clamp_range(0.0..=f64::NAN)
-                                .max_decimals(0),
-                        );
+                    for (name, ripple, attenuation) in [
+                        (
+                            "Mean:",
+                            stats.passband_ripple.mean,
+                            stats.stopband_attenuation.mean,
+                        ),
+                        (
+                            "Min:",
+                            stats.passband_ripple.min,
+                            stats.stopband_attenuation.min,
+                        ),
+                        (
+                            "Max:",
+                            stats.passband_ripple.max,
+                            stats.stopband_attenuation.max,
+                        ),
+                        (
+                            "Std. Dev.:",
+                            stats.passband_ripple.std_dev,
+                            stats.stopband_attenuation.std_dev,
+                        ),
+                    ] {
+                        ui.label(name);
+                        ui.label(format!("{:.3}", ripple));
+                        ui.label(format!("{:.1}", attenuation));
+                        ui.end_row();
                     }
-                };
+                });
+            }
+        });
+    }
+
+    fn draw_titles_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Titles").show(ui, |ui| {
+            egui::Grid::new("titles").show(ui, |ui| {
+                for (i, name) in PLOT_NAMES.iter().enumerate() {
+                    ui.strong(*name);
+                    ui.end_row();
+
+                    ui.label("Title:");
+                    ui.text_edit_singleline(&mut self.plot_titles[i]);
+                    ui.end_row();
+
+                    ui.label("X Axis:");
+                    ui.text_edit_singleline(&mut self.plot_x_labels[i]);
+                    ui.end_row();
+
+                    ui.label("Y Axis:");
+                    ui.text_edit_singleline(&mut self.plot_y_labels[i]);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn draw_display_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Display").show(ui, |ui| {
+            egui::Grid::new("display").show(ui, |ui| {
+                let mut pixels_per_point = ui.ctx().pixels_per_point();
+                ui.label("Font Size:");
+                let response = ui.add(egui::Slider::new(&mut pixels_per_point, 0.5..=3.0));
+                // Rescaling while dragging moves the slider away from under the pointer
+                if response.changed() && !response.dragged() || response.drag_released() {
+                    self.pixels_per_point = Some(pixels_per_point);
+                }
                 ui.end_row();
 
-                match self.filter_def.filter {
-                    Filter::HighPass | Filter::LowPass => (),
-                    _ => {
-                        ui.label("High Cut Frequency (Hz):");
-                        ui.add(
-                            egui::DragValue::new(&mut self.filter_def.f_hi_cut)
-                                .speed(0.1)
-                                .clamp_range(0.0..=f64::NAN)
-                                .max_decimals(0),
-                        );
-                        ui.end_row();
+                ui.label("");
+                if ui.button("Use System Scale").clicked() {
+                    self.pixels_per_point = None;
+                    if let Some(native) = self.native_pixels_per_point {
+                        ui.ctx().set_pixels_per_point(native);
                     }
-                };
-
-                ui.label("Window Type:");
-                self.draw_window_combo_box(ui);
+                }
+                ui.end_row();
             });
+        });
 
-            ui.add_space(40.0);
-            ui.label("Plot");
-            ui.separator();
-            egui::Grid::new("plot_params").show(ui, |ui| {
-                ui.label("Plot Response:");
-                egui::ComboBox::from_id_source("plot_type")
-                    .selected_text(format!("{}", self.plot_type))
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.plot_type, PlotType::Impulse, "Impulse");
-                        ui.selectable_value(&mut self.plot_type, PlotType::Step, "Step");
-                    });
+        if let Some(pixels_per_point) = self.pixels_per_point {
+            if pixels_per_point != ui.ctx().pixels_per_point() {
+                ui.ctx().set_pixels_per_point(pixels_per_point);
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    fn draw_debug_section(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Debug").show(ui, |ui| {
+            egui::Grid::new("debug").show(ui, |ui| {
+                ui.label("Gain:");
+                ui.label(format!("{}", self.filter_def.compute_sinc_gain()));
                 ui.end_row();
 
-                ui.label("Show Window:");
-                ui.checkbox(&mut self.show_window, "");
+                ui.label("Zero Crossings:");
+                ui.label(format!(
+                    "{}",
+                    FilterDef::count_zero_crossings(&self.filter_data.f_windowed)
+                ));
+                ui.end_row();
             });
 
-            ui.add_space(40.0);
-            ui.label("File");
-            ui.separator();
-            ui.with_layout(
-                egui::Layout::top_down_justified(egui::Align::Center),
-                |ui| {
-                    if ui.button("Export Filter").clicked() {
-                        println! {"{:?}", self.filter_data.f_windowed};
-                    }
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("debug_coefficients")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("n");
+                            ui.label("Filter");
+                            ui.label("Window");
+                            ui.end_row();
 
-                    if ui.button("Save Plots").clicked() {};
-                },
-            );
+                            let coefficients =
+                                self.filter_data.filter.iter().zip(&self.filter_data.window);
+                            for (n, (f, w)) in coefficients.enumerate() {
+                                ui.label(format!("{}", n));
+                                ui.label(format!("{}", f));
+                                ui.label(format!("{}", w));
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            egui::CollapsingHeader::new("Convolution Matrix").show(ui, |ui| {
+                ui.label(format!(
+                    "H for a signal of {} samples, positive taps red, negative taps blue",
+                    CONVOLUTION_MATRIX_SIGNAL_LEN
+                ));
+                App::draw_convolution_matrix(
+                    ui,
+                    &self
+                        .filter_def
+                        .convolution_matrix(CONVOLUTION_MATRIX_SIGNAL_LEN),
+                );
+            });
         });
+    }
+
+    /// Paints a matrix as a grid of cells shaded by the magnitude and sign of the entries.
+    #[cfg(debug_assertions)]
+    fn draw_convolution_matrix(ui: &mut egui::Ui, matrix: &[Vec<f64>]) {
+        let max = matrix
+            .iter()
+            .flatten()
+            .fold(0.0, |max: f64, h| max.max(h.abs()));
+        let n_columns = matrix.first().map_or(0, Vec::len);
+        let cell = (300.0 / matrix.len().max(1) as f32).clamp(1.0, 8.0);
+
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(cell * n_columns as f32, cell * matrix.len() as f32),
+            egui::Sense::hover(),
+        );
+        for (row, entries) in matrix.iter().enumerate() {
+            for (column, h) in entries.iter().enumerate() {
+                let t = if max > 0.0 { h.abs() / max } else { 0.0 };
+                let level = (255.0 * t) as u8;
+                let color = if *h >= 0.0 {
+                    egui::Color32::from_rgb(level, 0, 0)
+                } else {
+                    egui::Color32::from_rgb(0, 0, level)
+                };
+                let min = response.rect.min + egui::vec2(column as f32, row as f32) * cell;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, egui::vec2(cell, cell)),
+                    0.0,
+                    color,
+                );
+            }
+        }
+    }
+
+    fn export_test_vectors(&self) {
+        let (input, output) = self.filter_def.generate_test_vectors(TEST_VECTOR_LEN);
+        let output = if self.zero_phase_output {
+            FilterDef::zero_phase_filter(&self.filter_data.f_windowed, &input)
+        } else {
+            output
+        };
+        let files = [
+            ("test_vectors_input.csv", export::to_csv(&input, "input")),
+            ("test_vectors_output.csv", export::to_csv(&output, "output")),
+        ];
+
+        for (path, csv) in files {
+            match std::fs::write(path, csv) {
+                Ok(()) => println!("wrote {}", path),
+                Err(err) => eprintln!("error: could not write {}: {}", path, err),
+            }
+        }
+    }
+
+    fn export_frequency_response(&self) {
+        let table = self
+            .filter_def
+            .compute_frequency_response_table(FREQUENCY_RESPONSE_POINTS);
+
+        match std::fs::write(
+            FREQUENCY_RESPONSE_PATH,
+            export::to_frequency_response_csv(&table),
+        ) {
+            Ok(()) => println!("wrote {}", FREQUENCY_RESPONSE_PATH),
+            Err(err) => eprintln!(
+                "error: could not write {}: {}",
+                FREQUENCY_RESPONSE_PATH, err
+            ),
+        }
+    }
+
+    fn draw_center_panel(&mut self, ctx: &egui::Context) {
+        let reset_freq_zoom = std::mem::take(&mut self.reset_freq_zoom);
+        let reset_window_zoom = std::mem::take(&mut self.reset_window_zoom);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             let mut plot_width = ui.max_rect().width() - 4.0 * ui.spacing().item_spacing.x;
-            let plot_height = (ui.max_rect().height() - 20.0 * ui.spacing().item_spacing.y) / 2.0;
+            let mut plot_height =
+                (ui.max_rect().height() - 20.0 * ui.spacing().item_spacing.y) / 2.0;
 
             if self.show_window {
                 plot_width *= 0.5;
             }
 
+            // Shrink whichever dimension is too large so the plots keep the ratio inside the panel
+            if self.lock_aspect_ratio {
+                let ratio = self.aspect_ratio.value();
+                if plot_width / ratio > plot_height {
+                    plot_width = plot_height * ratio;
+                } else {
+                    plot_height = plot_width / ratio;
+                }
+            }
+
             ui.with_layout(egui::Layout::left_to_right(egui::Align::TOP), |ui| {
                 ui.scope(|ui| {
                     ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                        ui.label("Filter Response (Time Domain)");
-                        let plot_filter_resp_time = Plot::new("filter_resp_time")
+                        ui.label(&self.plot_titles[0]);
+                        let mut plot_filter_resp_time = Plot::new("filter_resp_time")
                             .width(plot_width)
                             .height(plot_height)
                             .allow_scroll(false)
-                            .x_axis_label("Time (s)")
+                            .x_axis_label(self.time_axis_label())
+                            .y_axis_label(&self.plot_y_labels[0])
                             .y_axis_width(3)
                             .legend(
                                 egui_plot::Legend::default().text_style(egui::TextStyle::Small),
                             );
+                        if self.reset_time_zoom {
+                            plot_filter_resp_time = plot_filter_resp_time.reset();
+                            self.reset_time_zoom = false;
+                        }
 
-                        plot_filter_resp_time.show(ui, |plot_ui| match self.plot_type {
-                            PlotType::Impulse => {
-                                plot_ui.line(
-                                    Line::new(self.filter_data.filter_imp.clone()).name("Filter"),
-                                );
-                                plot_ui.line(
-                                    Line::new(self.filter_data.f_windowed_imp.clone())
-                                        .name("Windowed"),
-                                );
+                        let plot_response = plot_filter_resp_time.show(ui, |plot_ui| {
+                            if self.lock_y_axis {
+                                let bounds = plot_ui.plot_bounds();
+                                plot_ui.set_plot_bounds(PlotBounds::from_min_max(
+                                    [bounds.min()[0], self.time_y_range[0]],
+                                    [bounds.max()[0], self.time_y_range[1]],
+                                ));
                             }
-                            PlotType::Step => {
-                                plot_ui.line(
-                                    Line::new(self.filter_data.filter_stp.clone()).name("Filter"),
-                                );
-                                plot_ui.line(
-                                    Line::new(self.filter_data.f_windowed_stp.clone())
-                                        .name("Windowed"),
-                                );
+
+                            match self.plot_type {
+                                PlotType::Impulse if self.time_response_style.is_discrete() => {
+                                    let f_windowed_imp = if self.normalize_amplitude {
+                                        &self.filter_data.f_normalized_imp
+                                    } else {
+                                        &self.filter_data.f_windowed_imp
+                                    };
+                                    self.draw_discrete(
+                                        plot_ui,
+                                        &self.filter_data.filter_imp,
+                                        "Filter",
+                                        Some(Curve::Filter),
+                                        0,
+                                    );
+                                    self.draw_discrete(
+                                        plot_ui,
+                                        f_windowed_imp,
+                                        "Windowed",
+                                        Some(Curve::Windowed),
+                                        1,
+                                    );
+                                    if let Some(f_min_phase) = self.visible_min_phase() {
+                                        self.draw_discrete(
+                                            plot_ui,
+                                            &data::plot_filter_imp(
+                                                f_min_phase,
+                                                self.filter_def.f_sampling,
+                                            ),
+                                            "Minimum Phase",
+                                            None,
+                                            2,
+                                        );
+                                    }
+                                    if let Some(cascade_data) = &self.cascade_data {
+                                        self.draw_discrete(
+                                            plot_ui,
+                                            &cascade_data.f_windowed_imp,
+                                            "Cascade",
+                                            None,
+                                            3,
+                                        );
+                                    }
+                                }
+                                PlotType::Impulse => {
+                                    plot_ui.line(
+                                        self.color_theme.style(
+                                            Line::new(self.filter_data.filter_imp.clone())
+                                                .name("Filter"),
+                                            Curve::Filter,
+                                        ),
+                                    );
+                                    let f_windowed_imp = if self.normalize_amplitude {
+                                        &self.filter_data.f_normalized_imp
+                                    } else {
+                                        &self.filter_data.f_windowed_imp
+                                    };
+                                    plot_ui.line(self.color_theme.style(
+                                        Line::new(f_windowed_imp.clone()).name("Windowed"),
+                                        Curve::Windowed,
+                                    ));
+                                    if let Some(f_min_phase) = self.visible_min_phase() {
+                                        plot_ui.line(
+                                            Line::new(data::plot_filter_imp(
+                                                f_min_phase,
+                                                self.filter_def.f_sampling,
+                                            ))
+                                            .name("Minimum Phase"),
+                                        );
+                                    }
+                                    if let Some(cascade_data) = &self.cascade_data {
+                                        plot_ui.line(
+                                            Line::new(cascade_data.f_windowed_imp.clone())
+                                                .name("Cascade"),
+                                        );
+                                    }
+                                }
+                                PlotType::Step => {
+                                    plot_ui.line(
+                                        self.color_theme.style(
+                                            Line::new(self.filter_data.filter_stp.clone())
+                                                .name("Filter"),
+                                            Curve::Filter,
+                                        ),
+                                    );
+                                    let f_windowed_stp = if self.normalize_amplitude {
+                                        &self.filter_data.f_normalized_stp
+                                    } else {
+                                        &self.filter_data.f_windowed_stp
+                                    };
+                                    plot_ui.line(self.color_theme.style(
+                                        Line::new(f_windowed_stp.clone()).name("Windowed"),
+                                        Curve::Windowed,
+                                    ));
+                                    if let Some(f_min_phase) = self.visible_min_phase() {
+                                        plot_ui.line(
+                                            Line::new(data::plot_filter_stp(
+                                                f_min_phase,
+                                                self.filter_def.f_sampling,
+                                            ))
+                                            .name("Minimum Phase"),
+                                        );
+                                    }
+                                    if let Some(cascade_data) = &self.cascade_data {
+                                        plot_ui.line(
+                                            Line::new(cascade_data.f_windowed_stp.clone())
+                                                .name("Cascade"),
+                                        );
+                                    }
+                                }
                             }
                         });
+                        if !self.lock_y_axis {
+                            let bounds = plot_response.transform.bounds();
+                            self.time_y_range = [bounds.min()[1], bounds.max()[1]];
+                        }
 
                         ui.add_space(10.0);
-                        ui.label("Filter Response (Frequency Domain)");
-                        let plot_filter_resp_freq = Plot::new("filter_resp_freq")
+                        ui.horizontal(|ui| {
+                            ui.label(&self.plot_titles[1]);
+                            if ui.button("Zoom to Transition Band").clicked() {
+                                self.freq_zoom = Some(self.transition_band_bounds());
+                            }
+                            if ui.button("Reset Zoom").clicked() {
+                                self.reset_freq_zoom = true;
+                            }
+                            if ui
+                                .toggle_value(
+                                    &mut self.high_res_band_edge,
+                                    "High-Resolution Band Edge",
+                                )
+                                .clicked()
+                            {
+                                if self.high_res_band_edge {
+                                    self.freq_zoom = Some(self.transition_band_bounds());
+                                } else {
+                                    self.band_edge_response = None;
+                                }
+                            }
+                        });
+                        let mut plot_filter_resp_freq = Plot::new("filter_resp_freq")
                             .width(plot_width)
                             .height(plot_height)
                             .allow_scroll(false)
-                            .x_axis_label("Frequency (Hz)")
-                            .y_axis_width(3)
+                            .x_axis_label(self.frequency_axis_label(1))
+                            .y_axis_label(&self.plot_y_labels[1])
                             .legend(
                                 egui_plot::Legend::default().text_style(egui::TextStyle::Small),
                             );
 
+                        // The PSD is drawn on the dB scale of the magnitude response, the right
+                        // axis converts the ticks back to linear PSD units
+                        if self.show_psd {
+                            plot_filter_resp_freq = plot_filter_resp_freq.custom_y_axes(vec![
+                                AxisHints::default().label(&self.plot_y_labels[1]),
+                                AxisHints::default()
+                                    .label("PSD (1/Hz)")
+                                    .placement(HPlacement::Right)
+                                    .formatter(|tick, _, _| {
+                                        format!("{:.0e}", 10f64.powf(tick / 10.0))
+                                    }),
+                            ]);
+                        }
+                        if reset_freq_zoom {
+                            plot_filter_resp_freq = plot_filter_resp_freq.reset();
+                        }
+                        if self.normalize_nyquist {
+                            plot_filter_resp_freq = plot_filter_resp_freq
+                                .x_grid_spacer(uniform_grid_spacer(|_| [0.05, 0.25, 1.0]));
+                        }
+                        let plot_filter_resp_freq = plot_filter_resp_freq.y_axis_width(3);
+
                         plot_filter_resp_freq.show(ui, |plot_ui| {
+                            if let Some(bounds) = self.freq_zoom.take() {
+                                plot_ui.set_plot_bounds(bounds);
+                            }
                             plot_ui.line(
-                                Line::new(self.filter_data.filter_dft.clone()).name("Filter"),
-                            );
-                            plot_ui.line(
-                                Line::new(self.filter_data.f_windowed_dft.clone()).name("Windowed"),
+                                self.color_theme.style(
+                                    Line::new(self.frequency_points(
+                                        &self.filter_data.filter_dft.magnitude_db,
+                                    ))
+                                    .name("Filter"),
+                                    Curve::Filter,
+                                ),
                             );
+                            let f_windowed_dft = if self.window_correction {
+                                &self.filter_data.f_windowed_dft_corrected
+                            } else {
+                                &self.filter_data.f_windowed_dft.magnitude_db
+                            };
+                            plot_ui.line(self.color_theme.style(
+                                Line::new(self.frequency_points(f_windowed_dft)).name("Windowed"),
+                                Curve::Windowed,
+                            ));
+                            if let Some(band_edge_response) = &self.band_edge_response {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(band_edge_response))
+                                        .name("Windowed (Band Edge)"),
+                                );
+                            }
+                            if self.show_psd {
+                                let f_windowed_psd: Vec<[f64; 2]> = self
+                                    .filter_data
+                                    .f_windowed_psd
+                                    .iter()
+                                    .map(|[f, psd]| [*f, 10.0 * psd.log10()])
+                                    .collect();
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(&f_windowed_psd)).name("PSD"),
+                                );
+                            }
+                            if self.decimation_factor > 1 {
+                                let decimated = data::compute_decimated_spectrum(
+                                    &self.filter_data.f_windowed_dft.magnitude_db,
+                                    self.decimation_factor,
+                                );
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(&decimated))
+                                        .name("Post-Decimation Spectrum"),
+                                );
+                            }
+                            if let Some(target_response) = &self.target_response {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(target_response))
+                                        .name("Target"),
+                                );
+                            }
+                            if let Some(f_matched_dft) = &self.f_matched_dft {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(f_matched_dft)).name("Matched"),
+                                );
+                            }
+                            if let Some(f_morph_dft) = &self.f_morph_dft {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(f_morph_dft)).name("Morph"),
+                                );
+                            }
+                            if let Some(f_weighted_dft) = &self.f_weighted_dft {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(f_weighted_dft))
+                                        .name("Weighted"),
+                                );
+                            }
+                            if let Some(cascade_data) = &self.cascade_data {
+                                plot_ui.line(
+                                    Line::new(self.frequency_points(
+                                        &cascade_data.f_windowed_dft.magnitude_db,
+                                    ))
+                                    .name("Cascade"),
+                                );
+                            }
                         });
                     });
                 });
@@ -330,133 +2906,351 @@ impl eframe::App for App {
                     ui.separator();
                     ui.scope(|ui| {
                         ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
-                            ui.label("Window Function (Time Domain)");
-                            let plot_window_resp_time = Plot::new("window_resp_time")
+                            ui.label(&self.plot_titles[2]);
+                            let mut plot_window_resp_time = Plot::new("window_resp_time")
                                 .width(plot_width)
                                 .height(plot_height)
                                 .allow_scroll(false)
-                                .x_axis_label("Time (s)")
+                                .x_axis_label(&self.plot_x_labels[2])
+                                .y_axis_label(&self.plot_y_labels[2])
                                 .y_axis_width(3);
+                            if reset_window_zoom {
+                                plot_window_resp_time = plot_window_resp_time.reset();
+                            }
 
                             plot_window_resp_time.show(ui, |plot_ui| {
-                                plot_ui.line(Line::new(self.filter_data.window_fun.clone()));
+                                plot_ui.line(self.color_theme.style(
+                                    Line::new(self.filter_data.window_fun.clone()),
+                                    Curve::Window,
+                                ));
                             });
 
                             ui.add_space(10.0);
-                            ui.label("Window Function (Frequency Domain)");
-                            let plot_window_resp_freq = Plot::new("window_resp_freq")
+                            ui.label(&self.plot_titles[3]);
+                            let mut plot_window_resp_freq = Plot::new("window_resp_freq")
                                 .width(plot_width)
                                 .height(plot_height)
                                 .allow_scroll(false)
-                                .x_axis_label("Frequency (Hz)")
+                                .x_axis_label(self.frequency_axis_label(3))
+                                .y_axis_label(&self.plot_y_labels[3])
                                 .y_axis_width(3);
+                            if reset_freq_zoom {
+                                plot_window_resp_freq = plot_window_resp_freq.reset();
+                            }
+                            if self.normalize_nyquist {
+                                plot_window_resp_freq = plot_window_resp_freq
+                                    .x_grid_spacer(uniform_grid_spacer(|_| [0.05, 0.25, 1.0]));
+                            }
 
                             plot_window_resp_freq.show(ui, |plot_ui| {
-                                plot_ui.line(Line::new(self.filter_data.window_dft.clone()));
+                                plot_ui.line(self.color_theme.style(
+                                    Line::new(self.frequency_points(
+                                        &self.filter_data.window_dft.magnitude_db,
+                                    )),
+                                    Curve::Window,
+                                ));
                             });
                         });
                     });
                 }
             });
         });
+    }
 
-        if filter_def_prev != self.filter_def {
-            self.filter_data = FilterData::from(&self.filter_def);
+    /// Returns the plot bounds framing the transition bands of the filter with a 50% margin on the
+    /// cutoff frequencies.
+    fn transition_band_bounds(&self) -> PlotBounds {
+        let (f_lo, f_hi) = match &self.filter_def.filter {
+            Filter::LowPass => (self.filter_def.f_hi_cut, self.filter_def.f_hi_cut),
+            Filter::HighPass => (self.filter_def.f_lo_cut, self.filter_def.f_lo_cut),
+            Filter::BandPass | Filter::BandStop | Filter::Custom { .. } | Filter::Kernel(_) => {
+                (self.filter_def.f_lo_cut, self.filter_def.f_hi_cut)
+            }
+            Filter::Multiband { bands } => {
+                // The band edges between two bands, the outer ends of the bands are not transitions
+                let f_nyquist = self.filter_def.f_sampling / 2.0;
+                let edges = bands
+                    .iter()
+                    .flat_map(|(f_lo, f_hi, _)| [*f_lo, *f_hi])
+                    .filter(|f| *f > 0.0 && *f < f_nyquist);
+                edges.fold((f_nyquist, 0.0), |(lo, hi), f| {
+                    (f64::min(lo, f), f64::max(hi, f))
+                })
+            }
+        };
+        let x_min = f_lo * 0.5;
+        let x_max = (f_hi * 1.5).min(self.filter_def.f_sampling / 2.0);
+
+        let (y_min, y_max) = self
+            .filter_data
+            .filter_dft
+            .magnitude_db
+            .iter()
+            .chain(&self.filter_data.f_windowed_dft.magnitude_db)
+            .filter(|[f, db]| *f >= x_min && *f <= x_max && db.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), [_, db]| {
+                (min.min(*db), max.max(*db))
+            });
+
+        let scale = self.frequency_scale();
+        if y_min.is_finite() && y_max.is_finite() {
+            PlotBounds::from_min_max([x_min * scale, y_min], [x_max * scale, y_max])
+        } else {
+            PlotBounds::from_min_max([x_min * scale, -100.0], [x_max * scale, 0.0])
         }
     }
-}
 
-fn plot_window(w: &Vec<f64>, f_sampling: f64) -> Vec<[f64; 2]> {
-    let dt = 1.0 / f_sampling;
+    /// Returns the factor from Hz to the units of the frequency axes.
+    fn frequency_scale(&self) -> f64 {
+        if self.normalize_nyquist {
+            2.0 / self.filter_def.f_sampling
+        } else {
+            1.0
+        }
+    }
 
-    let mut n = 0;
-    w.into_iter()
-        .map(|w| {
-            let t = n as f64 * dt;
-            n += 1;
+    /// Rescales the frequencies of `[Hz, y]` points to the units of the frequency axes.
+    /// Evaluates the windowed response on a coarse grid across the whole band, refined within the
+    /// frequency range of [`App::transition_band_bounds`].
+    fn compute_band_edge_response(&self) -> Vec<[f64; 2]> {
+        let f_nyquist = self.filter_def.f_sampling / 2.0;
+        let bounds = self.transition_band_bounds();
+        let (f_edge_lo, f_edge_hi) = (bounds.min()[0], bounds.max()[0]);
+        let (n_edge, n_band) = BAND_EDGE_POINTS;
 
-            [t, *w]
-        })
-        .collect()
-}
+        let mut frequencies: Vec<f64> = (0..n_band)
+            .map(|k| k as f64 / (n_band - 1) as f64 * f_nyquist)
+            .filter(|f| *f < f_edge_lo || *f > f_edge_hi)
+            .chain(
+                (0..n_edge)
+                    .map(|k| f_edge_lo + k as f64 / (n_edge - 1) as f64 * (f_edge_hi - f_edge_lo)),
+            )
+            .collect();
+        frequencies.sort_by(f64::total_cmp);
 
-fn plot_filter_imp(f: &Vec<f64>, f_sampling: f64) -> Vec<[f64; 2]> {
-    let dt = 1.0 / f_sampling;
+        let magnitude_db = FilterDef::compute_magnitude_at_frequencies(
+            &self.filter_data.f_windowed,
+            &frequencies,
+            self.filter_def.f_sampling,
+        );
+        frequencies
+            .into_iter()
+            .zip(magnitude_db)
+            .map(|(f, db)| [f, db])
+            .collect()
+    }
 
-    let mut n = 0;
-    f.into_iter()
-        .map(|f| {
-            let t = n as f64 * dt;
-            n += 1;
+    fn frequency_points(&self, points: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        let scale = self.frequency_scale();
 
-            [t, f * dt]
-        })
-        .collect()
+        points.iter().map(|[f, y]| [f * scale, *y]).collect()
+    }
+
+    fn time_axis_label(&self) -> &str {
+        if self.plot_type == PlotType::Impulse && self.time_response_style.is_discrete() {
+            SAMPLE_INDEX_LABEL
+        } else {
+            &self.plot_x_labels[0]
+        }
+    }
+
+    /// Draws `[t, y]` points over their sample index as stems or bars.
+    ///
+    /// Without a theme color the `index`-th automatic plot color is used, so the stems and their
+    /// markers match and the curves keep the colors of the connected style.
+    fn draw_discrete(
+        &self,
+        plot_ui: &mut egui_plot::PlotUi,
+        points: &[[f64; 2]],
+        name: &str,
+        curve: Option<Curve>,
+        index: usize,
+    ) {
+        let color = curve
+            .and_then(|curve| self.color_theme.color(curve))
+            .unwrap_or_else(|| {
+                // Same sequence as the automatic colors of egui_plot
+                let golden_ratio = (5f32.sqrt() - 1.0) / 2.0;
+                egui::ecolor::Hsva::new(index as f32 * golden_ratio, 0.85, 0.5, 1.0).into()
+            });
+        let width = match self.time_response_style {
+            TimeResponseStyle::Bar => 0.8,
+            _ => 0.05,
+        };
+
+        let bars = points
+            .iter()
+            .enumerate()
+            .map(|(n, [_, y])| Bar::new(n as f64, *y).width(width))
+            .collect();
+        plot_ui.bar_chart(BarChart::new(bars).name(name).color(color));
+
+        if self.time_response_style == TimeResponseStyle::Stem {
+            let tips: Vec<[f64; 2]> = points
+                .iter()
+                .enumerate()
+                .map(|(n, [_, y])| [n as f64, *y])
+                .collect();
+            plot_ui.points(Points::new(tips).radius(2.5).name(name).color(color));
+        }
+    }
+
+    fn frequency_axis_label(&self, plot: usize) -> &str {
+        if self.normalize_nyquist {
+            NORMALIZED_FREQUENCY_LABEL
+        } else {
+            &self.plot_x_labels[plot]
+        }
+    }
+
+    fn visible_min_phase(&self) -> Option<&[f64]> {
+        self.f_min_phase.as_deref().filter(|_| self.show_min_phase)
+    }
+
+    fn draw_status_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} | {}",
+                self.filter_def.filter, self.filter_def.window
+            ));
+            ui.separator();
+            ui.label(format!("Length: {}", self.filter_def.len));
+            ui.separator();
+            ui.label(format!("Shift: {}", self.filter_def.shift));
+            ui.separator();
+            ui.label(format!(
+                "3 dB Bandwidth: {:.1} Hz",
+                self.filter_data.bandwidth_3db
+            ));
+            ui.separator();
+            ui.label(format!(
+                "Passband Ripple: {:.1} dB",
+                self.filter_data.metrics.passband_ripple_db
+            ));
+            ui.separator();
+            ui.label(format!(
+                "Stopband Attenuation: {:.1} dB",
+                self.filter_data.metrics.stopband_attenuation_db
+            ));
+        });
+    }
 }
 
-fn plot_filter_stp(f: &Vec<f64>, f_sampling: f64) -> Vec<[f64; 2]> {
-    let dt = 1.0 / f_sampling;
+impl eframe::App for App {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        App::save_json(storage, FILTER_DEF_KEY, &self.filter_def);
+        App::save_json(storage, RECENT_DESIGNS_KEY, &self.recent_designs);
+        App::save_json(
+            storage,
+            PLOT_LABELS_KEY,
+            &[&self.plot_titles, &self.plot_x_labels, &self.plot_y_labels],
+        );
+        App::save_json(storage, PIXELS_PER_POINT_KEY, &self.pixels_per_point);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let filter_def_prev = self.filter_def.clone();
 
-    let mut n = 0;
-    let mut f_prev = 0.0;
-    let mut y = 0.0;
-    f.into_iter()
-        .map(|f| {
-            let t = n as f64 * dt;
-            n += 1;
+        if std::mem::take(&mut self.screenshot_pending) {
+            frame.request_screenshot();
+        }
 
-            y += f_prev + dt * 0.5 * (f + f_prev);
-            f_prev = *f;
+        self.navigate_parameters(ctx);
 
-            [t, y]
-        })
-        .collect()
+        // Home would otherwise also move the cursor of a focused text field
+        if !ctx.wants_keyboard_input() && ctx.input(|i| i.key_pressed(egui::Key::Home)) {
+            self.reset_all_zoom();
+        }
+
+        self.draw_side_panel(ctx);
+
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            self.draw_status_bar(ui);
+        });
+
+        self.draw_center_panel(ctx);
+        self.draw_autocorrelation_window(ctx);
+        self.draw_phase_window(ctx);
+        self.draw_pole_zero_window(ctx);
+        self.draw_chirp_test_window(ctx);
+        self.draw_design_space_window(ctx);
+        self.draw_recommendation_window(ctx);
+
+        if filter_def_prev != self.filter_def {
+            self.compute_filter_data();
+            self.f_min_phase = None;
+            self.zeros = None;
+            self.chirp_test = None;
+            self.band_edge_response = None;
+            self.quantization_stats = None;
+            self.compute_matched_response();
+            self.compute_morph_response();
+            self.compute_weighted_response();
+            self.compute_cascade();
+
+            // The plots of the new design are only drawn in the next frame
+            if self.screenshot_on_parameter_change {
+                self.screenshot_pending = true;
+                ctx.request_repaint();
+            }
+
+            #[cfg(feature = "audio")]
+            if let Some(audio_monitor) = &self.audio_monitor {
+                audio_monitor.set_coefficients(self.filter_data.f_windowed.clone());
+            }
+        }
+
+        // The minimum phase conversion is comparatively slow, only compute it when shown
+        if self.show_min_phase && self.f_min_phase.is_none() {
+            self.f_min_phase = Some(self.filter_def.minimum_phase_equivalent());
+        }
+        if self.show_pole_zero && self.zeros.is_none() {
+            self.zeros = Some(FilterDef::compute_zeros(&self.filter_data.f_windowed));
+        }
+        if self.high_res_band_edge && self.band_edge_response.is_none() {
+            self.band_edge_response = Some(self.compute_band_edge_response());
+        }
+        if self.show_chirp_test && self.chirp_test.is_none() {
+            self.chirp_test = Some(self.filter_def.apply_to_chirp(TEST_VECTOR_LEN));
+        }
+        if self.show_design_space {
+            self.update_design_space(ctx);
+        }
+    }
+
+    fn post_rendering(&mut self, _window_size_px: [u32; 2], frame: &eframe::Frame) {
+        if let Some(screenshot) = frame.screenshot() {
+            self.save_screenshot(&screenshot);
+        }
+    }
 }
 
-/// Returns the amplitude of the DFT of a signal.
-///
-/// The index `$m$` runs from 0 to `$\frac{N}{2}$`. This automatically discards the negative frequency
-/// components produced by the DFT. Considering that for this use case, the filter length will be less
-/// than `$N$`, the index `$n$` running over the filter length effectly results in a zero padded signal.
-///
-/// Instead of using the complex function:
-///
-/// ```math
-/// e^{-j2\pi nm/N}
-/// ```
-/// Eulers formula is used:
-/// ```math
-/// c_m[n] = \cos(2\pi mn/N) \\
-/// s_m[n] = \sin(2\pi mn/N)
-/// ```
-///
-/// [\[1\]](https://hal.science/hal-04075823/document) Laurent Nony, Jean-Marc Themlin.
-/// An introduction to the Discrete Fourier Transform and its applications in signal processing. Master. France. 2023. hal-04075823
-///
-/// [\[2\]](http://www.dspguide.com/pdfbook.htm) Steven W. Smith.
-/// The Scientist and Engineer's Guide to Digital Signal Processing
-fn plot_dft(signal: &Vec<f64>, f_sampling: f64) -> Vec<[f64; 2]> {
-    let df = f_sampling / (DFT_LEN) as f64;
-    let f: Vec<f64> = (0..DFT_LEN / 2).map(|n| n as f64 * df).collect();
-
-    let dft: Vec<f64> = (0..DFT_LEN / 2)
-        .map(|m| {
-            let mut n = 0;
-            let (re, im) = signal.into_iter().fold((0.0, 0.0), |(mut re, mut im), x| {
-                let theta = 2.0 * PI * (m * n) as f64 / DFT_LEN as f64;
-
-                n += 1;
-                re += x * (theta).cos();
-                im -= x * (theta).sin();
-
-                (re, im)
-            });
+/// Maps a stopband attenuation onto a blue to red color scale, leaving invalid designs transparent.
+fn attenuation_color(attenuation_db: f64) -> egui::Color32 {
+    if !attenuation_db.is_finite() {
+        return egui::Color32::TRANSPARENT;
+    }
 
-            // Calculate the DFT magnitude in dB
-            20.0 * ((re.powi(2) + im.powi(2)).sqrt()).log10()
-        })
-        .collect();
+    let t = (attenuation_db / DESIGN_SPACE_MAX_ATTENUATION_DB).clamp(0.0, 1.0);
+    egui::Color32::from_rgb((255.0 * t) as u8, 0, (255.0 * (1.0 - t)) as u8)
+}
+
+/// Returns the current UTC date as `YYYY-MM-DD`.
+fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    // Civil date from the days since 1970-01-01, see Howard Hinnant's `civil_from_days`
+    let z = (secs / 86400) as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
 
-    let plot: Vec<[f64; 2]> = f.iter().zip(dft).map(|(f, y)| [*f, y]).collect();
-    plot
+    format!("{:04}-{:02}-{:02}", year, month, day)
 }
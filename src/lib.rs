@@ -0,0 +1,9 @@
+pub mod binary;
+pub mod data;
+pub mod diff;
+pub mod export;
+pub mod ffi;
+pub mod filter_string;
+pub mod fir;
+#[cfg(feature = "wav")]
+pub mod wav;
@@ -9,15 +9,46 @@
 //!   \end{pmatrix}.
 //!   ```
 
-mod fir;
+#[cfg(feature = "audio")]
+mod audio;
 mod gui;
 
+use fir_designer::export;
+use fir_designer::fir::FilterDef;
+
 fn main() -> Result<(), eframe::Error> {
+    // `--headless <filter string>` prints the design summary instead of opening the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [flag, filter_string] = args.as_slice() {
+        if flag == "--headless" {
+            run_headless(filter_string);
+            return Ok(());
+        }
+    }
+
+    // `--screenshot-on-change` saves a PNG of the window whenever the design changes
+    let screenshot_on_change = args.iter().any(|arg| arg == "--screenshot-on-change");
+
     let options = eframe::NativeOptions::default();
 
     eframe::run_native(
         "FIR Filter Designer",
         options,
-        Box::new(|cc| Box::new(gui::App::new(cc))),
+        Box::new(move |cc| {
+            Box::new(gui::App::new(cc).with_screenshot_on_parameter_change(screenshot_on_change))
+        }),
     )
 }
+
+fn run_headless(filter_string: &str) {
+    match FilterDef::from_filter_string(filter_string) {
+        Ok(def) => print!(
+            "{}",
+            export::format_design_summary(&def, &def.compute_all_metrics())
+        ),
+        Err(e) => {
+            eprintln!("invalid filter string: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Reading and writing mono WAV files of test signals.
+
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum WavError {
+    Hound(hound::Error),
+    /// The sample format is neither 16 bit PCM nor 32 bit float.
+    UnsupportedFormat {
+        bits_per_sample: u16,
+        sample_format: hound::SampleFormat,
+    },
+}
+
+impl fmt::Display for WavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hound(e) => write!(f, "{}", e),
+            Self::UnsupportedFormat {
+                bits_per_sample,
+                sample_format,
+            } => write!(
+                f,
+                "unsupported sample format: {} bit {:?}, expected 16 bit PCM or 32 bit float",
+                bits_per_sample, sample_format
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WavError {}
+
+impl From<hound::Error> for WavError {
+    fn from(e: hound::Error) -> Self {
+        Self::Hound(e)
+    }
+}
+
+/// Reads a 16 bit PCM or 32 bit float WAV file, returning the samples normalized to `$[-1, 1]$`
+/// and the sample rate.
+///
+/// Files with several channels are downmixed to mono by averaging the channels.
+pub fn read_wav(path: &Path) -> Result<(Vec<f64>, u32), WavError> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+
+    let samples: Vec<f64> = match (spec.sample_format, spec.bits_per_sample) {
+        (hound::SampleFormat::Int, 16) => reader
+            .samples::<i16>()
+            .map(|x| x.map(|x| x as f64 / -(i16::MIN as f64)))
+            .collect::<Result<_, _>>()?,
+        (hound::SampleFormat::Float, 32) => reader
+            .samples::<f32>()
+            .map(|x| x.map(|x| x as f64))
+            .collect::<Result<_, _>>()?,
+        (sample_format, bits_per_sample) => {
+            return Err(WavError::UnsupportedFormat {
+                bits_per_sample,
+                sample_format,
+            })
+        }
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono = samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f64>() / channels as f64)
+        .collect();
+
+    Ok((mono, spec.sample_rate))
+}
+
+/// Writes the samples to a mono 32 bit float WAV file.
+pub fn write_wav(path: &Path, samples: &[f64], sample_rate: u32) -> Result<(), WavError> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for x in samples {
+        writer.write_sample(*x as f32)?;
+    }
+    writer.finalize()?;
+
+    Ok(())
+}
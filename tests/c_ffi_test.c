@@ -0,0 +1,66 @@
+/*
+ * Round trip test of the C interface, build and run from the repository root with
+ *
+ *   cargo build
+ *   cc tests/c_ffi_test.c -Iinclude -Ltarget/debug -lfir_designer -lm -o target/c_ffi_test
+ *   LD_LIBRARY_PATH=target/debug target/c_ffi_test
+ */
+
+#include <math.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "fir_designer.h"
+
+#define CHECK(cond)                                                   \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,    \
+                    __LINE__, #cond);                                 \
+            exit(1);                                                  \
+        }                                                             \
+    } while (0)
+
+typedef double *(*design_fn)(double, double, double, size_t, size_t, int, size_t *);
+
+int main(void) {
+    design_fn designs[] = {fir_design_lowpass, fir_design_highpass, fir_design_bandpass,
+                           fir_design_bandstop};
+    size_t len = 0;
+
+    for (size_t d = 0; d < sizeof(designs) / sizeof(designs[0]); d++) {
+        for (int window = FIR_WINDOW_RECTANGULAR; window <= FIR_WINDOW_FLAT_TOP; window++) {
+            double *h = designs[d](1000.0, 100.0, 300.0, 65, 32, window, &len);
+            CHECK(h != NULL);
+            CHECK(len == 65);
+
+            /* A centered odd length design has linear phase */
+            for (size_t n = 0; n < len; n++) {
+                CHECK(fabs(h[n] - h[len - 1 - n]) < 1e-12);
+            }
+
+            /* Filtering an impulse returns the coefficients */
+            double impulse[128] = {1.0};
+            size_t out_len = 0;
+            double *y = fir_apply(h, len, impulse, 128, &out_len);
+            CHECK(y != NULL);
+            CHECK(out_len == 128);
+            for (size_t n = 0; n < out_len; n++) {
+                CHECK(fabs(y[n] - (n < len ? h[n] : 0.0)) < 1e-12);
+            }
+
+            fir_free(y, out_len);
+            fir_free(h, len);
+        }
+    }
+
+    CHECK(fir_design_lowpass(1000.0, 100.0, 300.0, 65, 32, -1, &len) == NULL);
+    /* Zero bandwidth designs have no passband gain to normalize by */
+    CHECK(fir_design_bandpass(1000.0, 200.0, 200.0, 65, 32, FIR_WINDOW_HANN, &len) == NULL);
+    CHECK(fir_design_bandstop(1000.0, 0.0, 300.0, 65, 32, FIR_WINDOW_HANN, &len) == NULL);
+    CHECK(fir_apply(NULL, 0, NULL, 0, &len) == NULL);
+    fir_free(NULL, 0);
+
+    printf("c_ffi_test: ok\n");
+    return 0;
+}